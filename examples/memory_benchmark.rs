@@ -0,0 +1,117 @@
+/*!
+ * Primerjava porabe pomnilnika med parse_one (ClassFile) in parse_class_file_ref (ClassFileRef) za vse .class datoteke
+ * v danih mapah. JAR je treba prej razširiti, npr. `unzip -q rt.jar -d rt`, nato pa
+ * `cargo run --release --example memory_benchmark -- rt`.
+ *
+ * Datoteke se preberejo v pomnilnik pred meritvijo, zato se šteje le to, kar alocira parser. Pri ClassFileRef se za
+ * vsako metodo razčleni tudi Code atribut, da je primerjava za analizo bytecoda poštena.
+ */
+
+use std::{alloc::{GlobalAlloc, Layout, System}, env, fs, path::Path, sync::atomic::{AtomicUsize, Ordering}};
+
+use diploma::parser::{parse_class_file_ref, parse_one};
+
+/** Alokator, ki šteje alokacije, alocirane bajte in največjo hkratno porabo. */
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        let live_bytes = LIVE_BYTES.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK_LIVE_BYTES.fetch_max(live_bytes, Ordering::Relaxed);
+        return unsafe { System.alloc(layout) };
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { System.dealloc(ptr, layout) };
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[derive(Debug)]
+struct Measurement {
+    allocations: usize,
+    allocated_bytes: usize,
+    /** Največja poraba nad porabo ob začetku meritve, ko so vsi razčlenjeni razredi hkrati v pomnilniku */
+    peak_bytes: usize,
+    failed: usize,
+}
+
+/** Razčleni vse razrede s parse in jih obdrži do konca meritve, tako kot bi jih obdržala analiza. */
+fn measure<'a, T>(classes: &'a [Vec<u8>], parse: impl Fn(&'a [u8]) -> Option<T>) -> Measurement {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let allocated_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let live_bytes = LIVE_BYTES.load(Ordering::Relaxed);
+    PEAK_LIVE_BYTES.store(live_bytes, Ordering::Relaxed);
+
+    let mut parsed = Vec::with_capacity(classes.len());
+    let mut failed = 0;
+    for bytes in classes {
+        match parse(bytes) {
+            Some(class) => parsed.push(class),
+            None => failed += 1,
+        }
+    }
+
+    let measurement = Measurement {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - allocated_bytes,
+        peak_bytes: PEAK_LIVE_BYTES.load(Ordering::Relaxed) - live_bytes,
+        failed,
+    };
+    drop(parsed);
+
+    return measurement;
+}
+
+fn collect_class_files(path: &Path, classes: &mut Vec<Vec<u8>>) {
+    if path.is_dir() {
+        let mut entries: Vec<_> = fs::read_dir(path).unwrap().map(|entry| entry.unwrap().path()).collect();
+        entries.sort();
+        for entry in entries {
+            collect_class_files(&entry, classes);
+        }
+    }
+    else if path.extension().is_some_and(|extension| extension == "class") {
+        classes.push(fs::read(path).unwrap());
+    }
+}
+
+fn main() {
+    let mut classes = Vec::new();
+    for path in env::args().skip(1) {
+        collect_class_files(Path::new(&path), &mut classes);
+    }
+    if classes.is_empty() {
+        eprintln!("uporaba: memory_benchmark <mapa z .class datotekami>...");
+        std::process::exit(1);
+    }
+
+    let input_bytes: usize = classes.iter().map(Vec::len).sum();
+    println!("{} razredov, {} bajtov vhoda", classes.len(), input_bytes);
+
+    let owned = measure(&classes, |bytes| parse_one(&mut &bytes[..]).ok());
+    let borrowed = measure(&classes, |bytes| {
+        let class_file_ref = parse_class_file_ref(bytes).ok()?;
+        let mut codes = Vec::with_capacity(class_file_ref.methods.len());
+        for method in &class_file_ref.methods {
+            codes.push(method.code(&class_file_ref).ok()?);
+        }
+
+        return Some((class_file_ref, codes));
+    });
+
+    println!("{:<15} {:>12} {:>16} {:>16} {:>10}", "parser", "alokacije", "alocirani bajti", "največja poraba", "napake");
+    for (name, measurement) in [("ClassFile", &owned), ("ClassFileRef", &borrowed)] {
+        println!("{:<15} {:>12} {:>16} {:>16} {:>10}", name, measurement.allocations, measurement.allocated_bytes, measurement.peak_bytes, measurement.failed);
+    }
+}
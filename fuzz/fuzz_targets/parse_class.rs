@@ -0,0 +1,20 @@
+#![no_main]
+
+use std::io::Cursor;
+
+use diploma::parser;
+use libfuzzer_sys::fuzz_target;
+
+// Za semenski korpus so primerni razredi v testdata. Isto lastnost nad njimi in njihovimi mutacijami preverja tudi
+// test writer::tests::mutated_seed_corpus_round_trips, zato jo CI preveri brez cargo fuzz.
+// Parser mora za poljubne bajte vrniti Err, ne sme pa panicirati, uspešno prebran class pa se mora zapisati in prebrati nazaj v enak class
+fuzz_target!(|data: &[u8]| {
+    if let Ok(class_file) = parser::parse_one(&mut Cursor::new(data)) {
+        let bytes = class_file.to_bytes().unwrap();
+        assert_eq!(parser::parse_one(&mut Cursor::new(bytes)).unwrap(), class_file);
+    }
+
+    if let Ok(class_file_ref) = parser::parse_class_file_ref(data) {
+        let _ = class_file_ref.to_owned();
+    }
+});
@@ -0,0 +1,258 @@
+use core::{fmt, iter::Peekable, str::Chars};
+use alloc::{boxed::Box, string::{String, ToString}, vec::Vec};
+
+use crate::parser::{AttributeRuntimeAnnotationsEntry, ClassFile, FieldInfo, MethodInfo};
+
+/** Največje število dimenzij tabele (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.3.2) */
+const MAX_ARRAY_DIMENSIONS: usize = 255;
+
+/** https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.3.2 */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FieldType {
+    Byte,
+    Char,
+    Double,
+    Float,
+    Int,
+    Long,
+    Short,
+    Boolean,
+    /** Ime razreda v internal form, npr. `java/lang/String` */
+    Object(String),
+    Array(Box<FieldType>),
+}
+
+/** https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.3.3 */
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MethodDescriptor {
+    pub parameters: Vec<FieldType>,
+    /** None pomeni void */
+    pub return_type: Option<FieldType>,
+}
+
+/** Izpiše tip tako, kot bi bil zapisan v Javi, npr. `java.lang.String[]`. */
+impl fmt::Display for FieldType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldType::Byte => write!(f, "byte"),
+            FieldType::Char => write!(f, "char"),
+            FieldType::Double => write!(f, "double"),
+            FieldType::Float => write!(f, "float"),
+            FieldType::Int => write!(f, "int"),
+            FieldType::Long => write!(f, "long"),
+            FieldType::Short => write!(f, "short"),
+            FieldType::Boolean => write!(f, "boolean"),
+            FieldType::Object(class_name) => write!(f, "{}", class_name.replace('/', ".")),
+            FieldType::Array(component_type) => write!(f, "{}[]", component_type),
+        }
+    }
+}
+
+/** Razčleni deskriptor polja, npr. `[Ljava/lang/String;`. Vrne None, če deskriptor ni veljaven. */
+pub fn parse_field_descriptor(descriptor: &str) -> Option<FieldType> {
+    let mut chars = descriptor.chars().peekable();
+    let field_type = parse_field_type(&mut chars)?;
+    if chars.next().is_some() {
+        return None;
+    }
+
+    return Some(field_type);
+}
+
+/** Razčleni deskriptor metode, npr. `(II)Z`. Vrne None, če deskriptor ni veljaven. */
+pub fn parse_method_descriptor(descriptor: &str) -> Option<MethodDescriptor> {
+    let mut chars = descriptor.chars().peekable();
+    if chars.next()? != '(' {
+        return None;
+    }
+
+    let mut parameters = Vec::new();
+    while *chars.peek()? != ')' {
+        parameters.push(parse_field_type(&mut chars)?);
+    }
+    chars.next();
+
+    let return_type = if chars.peek() == Some(&'V') {
+        chars.next();
+        None
+    }
+    else {
+        Some(parse_field_type(&mut chars)?)
+    };
+
+    if chars.next().is_some() {
+        return None;
+    }
+
+    return Some(MethodDescriptor { parameters, return_type });
+}
+
+/** Število mest (slotov) na skladu oz. med lokalnimi spremenljivkami, ki jih zaseda vrednost tipa: long in double 2, ostali 1. */
+pub fn descriptor_slot_size(field_type: &FieldType) -> u8 {
+    return match field_type {
+        FieldType::Long | FieldType::Double => 2,
+        _ => 1,
+    };
+}
+
+impl MethodDescriptor {
+    /**
+     * Skupno število mest, ki jih zasedajo parametri. Pri statični metodi je to najmanjši max_locals, pri ostalih pa je
+     * treba prišteti še mesto za `this` (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.3.3).
+     * Veljaven deskriptor ima največ 255 mest, parser pa tega ne preverja, zato je rezultat u32 (deskriptor z 65535
+     * parametri tipa long zasede 131070 mest).
+     */
+    pub fn parameter_slot_count(&self) -> u32 {
+        return self.parameters.iter().map(|parameter| u32::from(descriptor_slot_size(parameter))).sum();
+    }
+}
+
+fn parse_field_type(chars: &mut Peekable<Chars>) -> Option<FieldType> {
+    let mut array_dimensions = 0;
+    while chars.peek() == Some(&'[') {
+        chars.next();
+        array_dimensions += 1;
+    }
+    if array_dimensions > MAX_ARRAY_DIMENSIONS {
+        return None;
+    }
+
+    let mut field_type = match chars.next()? {
+        'B' => FieldType::Byte,
+        'C' => FieldType::Char,
+        'D' => FieldType::Double,
+        'F' => FieldType::Float,
+        'I' => FieldType::Int,
+        'J' => FieldType::Long,
+        'S' => FieldType::Short,
+        'Z' => FieldType::Boolean,
+        'L' => {
+            let mut class_name = String::new();
+            loop {
+                match chars.next()? {
+                    ';' => break,
+                    c => class_name.push(c),
+                }
+            }
+            if class_name.is_empty() {
+                return None;
+            }
+
+            FieldType::Object(class_name)
+        },
+        _ => return None,
+    };
+
+    for _ in 0..array_dimensions {
+        field_type = FieldType::Array(Box::new(field_type));
+    }
+
+    return Some(field_type);
+}
+
+impl FieldInfo {
+    /** Razrešen in razčlenjen deskriptor polja. Vrne None, če descriptor_index ne kaže na CpUtf8 ali deskriptor ni veljaven. */
+    pub fn field_type(&self, class_file: &ClassFile) -> Option<FieldType> {
+        return parse_field_descriptor(class_file.get_utf8(self.descriptor_index)?);
+    }
+}
+
+impl MethodInfo {
+    /** Razrešen in razčlenjen deskriptor metode. Vrne None, če descriptor_index ne kaže na CpUtf8 ali deskriptor ni veljaven. */
+    pub fn descriptor_parsed(&self, class_file: &ClassFile) -> Option<MethodDescriptor> {
+        return parse_method_descriptor(class_file.get_utf8(self.descriptor_index)?);
+    }
+}
+
+impl AttributeRuntimeAnnotationsEntry {
+    /**
+     * Ime tipa anotacije v obliki s pikami, npr. `java.lang.Deprecated` za deskriptor `Ljava/lang/Deprecated;`. Vrne None,
+     * če type_index ne kaže na CpUtf8 ali deskriptor ni deskriptor razreda.
+     */
+    pub fn annotation_type(&self, class_file: &ClassFile) -> Option<String> {
+        let field_type @ FieldType::Object(_) = parse_field_descriptor(class_file.get_utf8(self.type_index)?)? else {
+            return None;
+        };
+
+        return Some(field_type.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixture::{u16s, ClassBuilder}, parser::AttributeInfo};
+
+    use super::*;
+
+    #[test]
+    fn long_and_double_take_two_slots() {
+        let descriptor = parse_method_descriptor("(IJLjava/lang/String;D[J[D)V").unwrap();
+
+        let slot_sizes: Vec<u8> = descriptor.parameters.iter().map(descriptor_slot_size).collect();
+
+        assert_eq!(slot_sizes, [1, 2, 1, 2, 1, 1]);
+        assert_eq!(descriptor.parameter_slot_count(), 8);
+    }
+
+    #[test]
+    fn parameter_slot_count_does_not_overflow() {
+        let descriptor = parse_method_descriptor(&format!("({})V", "J".repeat(40000))).unwrap();
+
+        assert_eq!(descriptor.parameter_slot_count(), 80000);
+    }
+
+    #[test]
+    fn method_descriptor_from_class_file() {
+        let mut builder = ClassBuilder::new("A");
+        builder.method(0x0401, "m", "(DJ)J", &[]);
+        builder.method(0x0401, "bad", "(J", &[]);
+        let class_file = builder.parse();
+
+        assert_eq!(class_file.methods[0].descriptor_parsed(&class_file).unwrap().parameter_slot_count(), 4);
+        assert!(class_file.methods[1].descriptor_parsed(&class_file).is_none());
+    }
+
+    #[test]
+    fn field_type_accessor() {
+        let mut builder = ClassBuilder::new("A");
+        builder.field(0x0002, "names", "[[Ljava/lang/String;", &[]);
+        builder.field(0x0002, "flag", "Z", &[]);
+        builder.field(0x0002, "bad", "Ljava/lang/String", &[]);
+        let class_file = builder.parse();
+
+        let field_type = class_file.fields[0].field_type(&class_file).unwrap();
+        assert_eq!(field_type, FieldType::Array(Box::new(FieldType::Array(Box::new(FieldType::Object("java/lang/String".into()))))));
+        assert_eq!(field_type.to_string(), "java.lang.String[][]");
+        assert_eq!(class_file.fields[1].field_type(&class_file), Some(FieldType::Boolean));
+        assert_eq!(class_file.fields[2].field_type(&class_file), None);
+    }
+
+    #[test]
+    fn invalid_descriptors_are_rejected() {
+        assert_eq!(parse_method_descriptor("(I)VV"), None);
+        assert_eq!(parse_method_descriptor("I)V"), None);
+        assert_eq!(parse_method_descriptor("(L;)V"), None);
+        assert_eq!(parse_field_descriptor("V"), None);
+        assert_eq!(parse_field_descriptor(&format!("{}I", "[".repeat(256))), None);
+        assert!(parse_field_descriptor(&format!("{}I", "[".repeat(255))).is_some());
+    }
+
+    #[test]
+    fn annotation_type_name() {
+        let mut builder = ClassBuilder::new("A");
+        let deprecated = builder.utf8("Ljava/lang/Deprecated;");
+        let nested = builder.utf8("Lp/Outer$Inner;");
+        let primitive = builder.utf8("I");
+        let info = u16s(&[3, deprecated, 0, nested, 0, primitive, 0]);
+        let annotations = builder.attribute("RuntimeVisibleAnnotations", &info);
+        builder.class_attribute(annotations);
+        let class_file = builder.parse();
+        let AttributeInfo::RuntimeVisibleAnnotations(attribute_annotations) = &class_file.attributes[0] else {
+            panic!("pričakovan RuntimeVisibleAnnotations");
+        };
+
+        let names: Vec<_> = attribute_annotations.annotations.iter().map(|annotation| annotation.annotation_type(&class_file)).collect();
+
+        assert_eq!(names, [Some("java.lang.Deprecated".into()), Some("p.Outer$Inner".into()), None]);
+    }
+}
@@ -0,0 +1,1154 @@
+use core::{error::Error, fmt::{self, Display, Formatter}};
+use alloc::{boxed::Box, collections::{BTreeMap, BTreeSet}, string::{String, ToString}, vec::Vec};
+use byteorder::BigEndian;
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, IntoStaticStr};
+
+use crate::{io::{Cursor, Read, ReadBytesExt}, descriptor::{parse_field_descriptor, parse_method_descriptor, FieldType}, parser::{AttributeCode, AttributeInfo, ClassFile, CpInfo, MethodHandleReferenceKind, MethodInfo}, resolve::{MemberRef, ResolvedMethodHandle}};
+
+/** Razred z bootstrap metodama za lambde in reference na metode */
+const LAMBDA_METAFACTORY: &str = "java/lang/invoke/LambdaMetafactory";
+
+/** https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-6.html#jvms-6.5 */
+#[derive(EnumIter, IntoStaticStr, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[strum(serialize_all = "snake_case")]
+#[repr(u8)]
+pub enum Opcode {
+    Nop = 0x00,
+    AconstNull = 0x01,
+    IconstM1 = 0x02,
+    #[strum(serialize = "iconst_0")]
+    Iconst0 = 0x03,
+    #[strum(serialize = "iconst_1")]
+    Iconst1 = 0x04,
+    #[strum(serialize = "iconst_2")]
+    Iconst2 = 0x05,
+    #[strum(serialize = "iconst_3")]
+    Iconst3 = 0x06,
+    #[strum(serialize = "iconst_4")]
+    Iconst4 = 0x07,
+    #[strum(serialize = "iconst_5")]
+    Iconst5 = 0x08,
+    #[strum(serialize = "lconst_0")]
+    Lconst0 = 0x09,
+    #[strum(serialize = "lconst_1")]
+    Lconst1 = 0x0a,
+    #[strum(serialize = "fconst_0")]
+    Fconst0 = 0x0b,
+    #[strum(serialize = "fconst_1")]
+    Fconst1 = 0x0c,
+    #[strum(serialize = "fconst_2")]
+    Fconst2 = 0x0d,
+    #[strum(serialize = "dconst_0")]
+    Dconst0 = 0x0e,
+    #[strum(serialize = "dconst_1")]
+    Dconst1 = 0x0f,
+    Bipush = 0x10,
+    Sipush = 0x11,
+    Ldc = 0x12,
+    LdcW = 0x13,
+    Ldc2W = 0x14,
+    Iload = 0x15,
+    Lload = 0x16,
+    Fload = 0x17,
+    Dload = 0x18,
+    Aload = 0x19,
+    #[strum(serialize = "iload_0")]
+    Iload0 = 0x1a,
+    #[strum(serialize = "iload_1")]
+    Iload1 = 0x1b,
+    #[strum(serialize = "iload_2")]
+    Iload2 = 0x1c,
+    #[strum(serialize = "iload_3")]
+    Iload3 = 0x1d,
+    #[strum(serialize = "lload_0")]
+    Lload0 = 0x1e,
+    #[strum(serialize = "lload_1")]
+    Lload1 = 0x1f,
+    #[strum(serialize = "lload_2")]
+    Lload2 = 0x20,
+    #[strum(serialize = "lload_3")]
+    Lload3 = 0x21,
+    #[strum(serialize = "fload_0")]
+    Fload0 = 0x22,
+    #[strum(serialize = "fload_1")]
+    Fload1 = 0x23,
+    #[strum(serialize = "fload_2")]
+    Fload2 = 0x24,
+    #[strum(serialize = "fload_3")]
+    Fload3 = 0x25,
+    #[strum(serialize = "dload_0")]
+    Dload0 = 0x26,
+    #[strum(serialize = "dload_1")]
+    Dload1 = 0x27,
+    #[strum(serialize = "dload_2")]
+    Dload2 = 0x28,
+    #[strum(serialize = "dload_3")]
+    Dload3 = 0x29,
+    #[strum(serialize = "aload_0")]
+    Aload0 = 0x2a,
+    #[strum(serialize = "aload_1")]
+    Aload1 = 0x2b,
+    #[strum(serialize = "aload_2")]
+    Aload2 = 0x2c,
+    #[strum(serialize = "aload_3")]
+    Aload3 = 0x2d,
+    Iaload = 0x2e,
+    Laload = 0x2f,
+    Faload = 0x30,
+    Daload = 0x31,
+    Aaload = 0x32,
+    Baload = 0x33,
+    Caload = 0x34,
+    Saload = 0x35,
+    Istore = 0x36,
+    Lstore = 0x37,
+    Fstore = 0x38,
+    Dstore = 0x39,
+    Astore = 0x3a,
+    #[strum(serialize = "istore_0")]
+    Istore0 = 0x3b,
+    #[strum(serialize = "istore_1")]
+    Istore1 = 0x3c,
+    #[strum(serialize = "istore_2")]
+    Istore2 = 0x3d,
+    #[strum(serialize = "istore_3")]
+    Istore3 = 0x3e,
+    #[strum(serialize = "lstore_0")]
+    Lstore0 = 0x3f,
+    #[strum(serialize = "lstore_1")]
+    Lstore1 = 0x40,
+    #[strum(serialize = "lstore_2")]
+    Lstore2 = 0x41,
+    #[strum(serialize = "lstore_3")]
+    Lstore3 = 0x42,
+    #[strum(serialize = "fstore_0")]
+    Fstore0 = 0x43,
+    #[strum(serialize = "fstore_1")]
+    Fstore1 = 0x44,
+    #[strum(serialize = "fstore_2")]
+    Fstore2 = 0x45,
+    #[strum(serialize = "fstore_3")]
+    Fstore3 = 0x46,
+    #[strum(serialize = "dstore_0")]
+    Dstore0 = 0x47,
+    #[strum(serialize = "dstore_1")]
+    Dstore1 = 0x48,
+    #[strum(serialize = "dstore_2")]
+    Dstore2 = 0x49,
+    #[strum(serialize = "dstore_3")]
+    Dstore3 = 0x4a,
+    #[strum(serialize = "astore_0")]
+    Astore0 = 0x4b,
+    #[strum(serialize = "astore_1")]
+    Astore1 = 0x4c,
+    #[strum(serialize = "astore_2")]
+    Astore2 = 0x4d,
+    #[strum(serialize = "astore_3")]
+    Astore3 = 0x4e,
+    Iastore = 0x4f,
+    Lastore = 0x50,
+    Fastore = 0x51,
+    Dastore = 0x52,
+    Aastore = 0x53,
+    Bastore = 0x54,
+    Castore = 0x55,
+    Sastore = 0x56,
+    Pop = 0x57,
+    Pop2 = 0x58,
+    Dup = 0x59,
+    DupX1 = 0x5a,
+    DupX2 = 0x5b,
+    Dup2 = 0x5c,
+    Dup2X1 = 0x5d,
+    Dup2X2 = 0x5e,
+    Swap = 0x5f,
+    Iadd = 0x60,
+    Ladd = 0x61,
+    Fadd = 0x62,
+    Dadd = 0x63,
+    Isub = 0x64,
+    Lsub = 0x65,
+    Fsub = 0x66,
+    Dsub = 0x67,
+    Imul = 0x68,
+    Lmul = 0x69,
+    Fmul = 0x6a,
+    Dmul = 0x6b,
+    Idiv = 0x6c,
+    Ldiv = 0x6d,
+    Fdiv = 0x6e,
+    Ddiv = 0x6f,
+    Irem = 0x70,
+    Lrem = 0x71,
+    Frem = 0x72,
+    Drem = 0x73,
+    Ineg = 0x74,
+    Lneg = 0x75,
+    Fneg = 0x76,
+    Dneg = 0x77,
+    Ishl = 0x78,
+    Lshl = 0x79,
+    Ishr = 0x7a,
+    Lshr = 0x7b,
+    Iushr = 0x7c,
+    Lushr = 0x7d,
+    Iand = 0x7e,
+    Land = 0x7f,
+    Ior = 0x80,
+    Lor = 0x81,
+    Ixor = 0x82,
+    Lxor = 0x83,
+    Iinc = 0x84,
+    I2l = 0x85,
+    I2f = 0x86,
+    I2d = 0x87,
+    L2i = 0x88,
+    L2f = 0x89,
+    L2d = 0x8a,
+    F2i = 0x8b,
+    F2l = 0x8c,
+    F2d = 0x8d,
+    D2i = 0x8e,
+    D2l = 0x8f,
+    D2f = 0x90,
+    I2b = 0x91,
+    I2c = 0x92,
+    I2s = 0x93,
+    Lcmp = 0x94,
+    Fcmpl = 0x95,
+    Fcmpg = 0x96,
+    Dcmpl = 0x97,
+    Dcmpg = 0x98,
+    Ifeq = 0x99,
+    Ifne = 0x9a,
+    Iflt = 0x9b,
+    Ifge = 0x9c,
+    Ifgt = 0x9d,
+    Ifle = 0x9e,
+    IfIcmpeq = 0x9f,
+    IfIcmpne = 0xa0,
+    IfIcmplt = 0xa1,
+    IfIcmpge = 0xa2,
+    IfIcmpgt = 0xa3,
+    IfIcmple = 0xa4,
+    IfAcmpeq = 0xa5,
+    IfAcmpne = 0xa6,
+    Goto = 0xa7,
+    Jsr = 0xa8,
+    Ret = 0xa9,
+    Tableswitch = 0xaa,
+    Lookupswitch = 0xab,
+    Ireturn = 0xac,
+    Lreturn = 0xad,
+    Freturn = 0xae,
+    Dreturn = 0xaf,
+    Areturn = 0xb0,
+    Return = 0xb1,
+    Getstatic = 0xb2,
+    Putstatic = 0xb3,
+    Getfield = 0xb4,
+    Putfield = 0xb5,
+    Invokevirtual = 0xb6,
+    Invokespecial = 0xb7,
+    Invokestatic = 0xb8,
+    Invokeinterface = 0xb9,
+    Invokedynamic = 0xba,
+    New = 0xbb,
+    Newarray = 0xbc,
+    Anewarray = 0xbd,
+    Arraylength = 0xbe,
+    Athrow = 0xbf,
+    Checkcast = 0xc0,
+    Instanceof = 0xc1,
+    Monitorenter = 0xc2,
+    Monitorexit = 0xc3,
+    Wide = 0xc4,
+    Multianewarray = 0xc5,
+    Ifnull = 0xc6,
+    Ifnonnull = 0xc7,
+    GotoW = 0xc8,
+    JsrW = 0xc9,
+}
+
+impl TryFrom<u8> for Opcode {
+    type Error = ();
+
+    fn try_from(v: u8) -> Result<Self, Self::Error> {
+        return Opcode::iter().find(|opcode| *opcode as u8 == v).ok_or(());
+    }
+}
+
+impl Opcode {
+    pub fn mnemonic(&self) -> &'static str {
+        return self.into();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MalformedBytecode {
+    msg: String,
+}
+
+impl Display for MalformedBytecode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Napaka v bytecodu: {}.", self.msg)
+    }
+}
+
+impl Error for MalformedBytecode {}
+
+impl MalformedBytecode {
+    fn truncated(offset: u32) -> MalformedBytecode {
+        return MalformedBytecode { msg: format!("ukaz na odmiku {} je okrnjen", offset) };
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operands {
+    None,
+    /** bipush */
+    Byte(i8),
+    /** sipush */
+    Short(i16),
+    /** Indeks lokalne spremenljivke pri load/store/ret, ki ga ukaz ne vsebuje implicitno */
+    LocalVariable(u16),
+    /** ldc, ldc_w, ldc2_w, getstatic, putstatic, getfield, putfield, invokevirtual, invokespecial, invokestatic, invokedynamic, new, anewarray, checkcast, instanceof */
+    ConstantPoolIndex(u16),
+    /** Odmik skoka, relativen na začetek ukaza */
+    Branch(i32),
+    Iinc { index: u16, constant: i16 },
+    InvokeInterface { index: u16, count: u8 },
+    MultiANewArray { index: u16, dimensions: u8 },
+    /** atype (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-6.html#jvms-6.5.newarray) */
+    NewArray(u8),
+    /** Odmiki so relativni na začetek ukaza */
+    TableSwitch { default: i32, low: i32, high: i32, offsets: Vec<i32> },
+    /** Odmiki so relativni na začetek ukaza */
+    LookupSwitch { default: i32, pairs: Vec<(i32, i32)> },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    pub offset: u32,
+    pub opcode: Opcode,
+    /** Ukaz je razširjen s predpono wide (opcode vsebuje razširjen ukaz) */
+    pub wide: bool,
+    pub operands: Operands,
+}
+
+impl Instruction {
+    /** Absolutni odmiki vseh ciljev skoka (pri switchih tudi default). */
+    pub fn branch_targets(&self) -> Vec<u32> {
+        let relative_targets = match &self.operands {
+            Operands::Branch(branch) => vec![*branch],
+            Operands::TableSwitch { default, offsets, .. } => {
+                let mut targets = vec![*default];
+                targets.extend(offsets);
+                targets
+            },
+            Operands::LookupSwitch { default, pairs } => {
+                let mut targets = vec![*default];
+                targets.extend(pairs.iter().map(|(_, offset)| offset));
+                targets
+            },
+            _ => vec![],
+        };
+
+        return relative_targets.into_iter().map(|relative| self.offset.wrapping_add_signed(relative)).collect();
+    }
+
+    /** Indeks v constant pool, če ga ukaz vsebuje. */
+    pub fn constant_pool_index(&self) -> Option<u16> {
+        match self.operands {
+            Operands::ConstantPoolIndex(index) => Some(index),
+            Operands::InvokeInterface { index, .. } => Some(index),
+            Operands::MultiANewArray { index, .. } => Some(index),
+            _ => None,
+        }
+    }
+
+    /** Indeks lokalne spremenljivke, ki jo ukaz bere ali piše, tudi kadar je implicitni (npr. 1 pri iload_1). */
+    pub fn local_variable_index(&self) -> Option<u16> {
+        if let Operands::LocalVariable(index) | Operands::Iinc { index, .. } = self.operands {
+            return Some(index);
+        }
+
+        let index = match self.opcode {
+            Opcode::Iload0 | Opcode::Lload0 | Opcode::Fload0 | Opcode::Dload0 | Opcode::Aload0 | Opcode::Istore0 | Opcode::Lstore0 | Opcode::Fstore0 | Opcode::Dstore0 | Opcode::Astore0 => 0,
+            Opcode::Iload1 | Opcode::Lload1 | Opcode::Fload1 | Opcode::Dload1 | Opcode::Aload1 | Opcode::Istore1 | Opcode::Lstore1 | Opcode::Fstore1 | Opcode::Dstore1 | Opcode::Astore1 => 1,
+            Opcode::Iload2 | Opcode::Lload2 | Opcode::Fload2 | Opcode::Dload2 | Opcode::Aload2 | Opcode::Istore2 | Opcode::Lstore2 | Opcode::Fstore2 | Opcode::Dstore2 | Opcode::Astore2 => 2,
+            Opcode::Iload3 | Opcode::Lload3 | Opcode::Fload3 | Opcode::Dload3 | Opcode::Aload3 | Opcode::Istore3 | Opcode::Lstore3 | Opcode::Fstore3 | Opcode::Dstore3 | Opcode::Astore3 => 3,
+            _ => return None,
+        };
+
+        return Some(index);
+    }
+
+    /** if*, if_icmp*, if_acmp*, ifnull in ifnonnull */
+    pub fn is_conditional_branch(&self) -> bool {
+        return matches!(self.opcode,
+            Opcode::Ifeq | Opcode::Ifne | Opcode::Iflt | Opcode::Ifge | Opcode::Ifgt | Opcode::Ifle |
+            Opcode::IfIcmpeq | Opcode::IfIcmpne | Opcode::IfIcmplt | Opcode::IfIcmpge | Opcode::IfIcmpgt | Opcode::IfIcmple |
+            Opcode::IfAcmpeq | Opcode::IfAcmpne | Opcode::Ifnull | Opcode::Ifnonnull);
+    }
+
+    /** Ali ukaz bere ali piše long oz. double, ki zaseda dve zaporedni mesti med lokalnimi spremenljivkami. */
+    pub fn uses_two_local_variable_slots(&self) -> bool {
+        return matches!(self.opcode,
+            Opcode::Lload | Opcode::Dload | Opcode::Lstore | Opcode::Dstore |
+            Opcode::Lload0 | Opcode::Lload1 | Opcode::Lload2 | Opcode::Lload3 | Opcode::Dload0 | Opcode::Dload1 | Opcode::Dload2 | Opcode::Dload3 |
+            Opcode::Lstore0 | Opcode::Lstore1 | Opcode::Lstore2 | Opcode::Lstore3 | Opcode::Dstore0 | Opcode::Dstore1 | Opcode::Dstore2 | Opcode::Dstore3);
+    }
+}
+
+impl AttributeCode {
+    /**
+     * Največji indeks lokalne spremenljivke, ki ga uporablja kateri od ukazov (pri long in double tudi drugo mesto).
+     * Prosto mesto za začasno spremenljivko je torej max + 1. Vrne None, če noben ukaz ne uporablja lokalnih spremenljivk
+     * (tudi pri prazni kodi) ali kode ni mogoče razčleniti. Parametri metode, ki jih koda ne uporabi, niso upoštevani.
+     */
+    pub fn max_local_slot_used(&self) -> Option<u16> {
+        let instructions = disassemble(&self.code).ok()?;
+
+        return instructions.iter().filter_map(|instruction| {
+            let index = instruction.local_variable_index()?;
+            if instruction.uses_two_local_variable_slots() {
+                return Some(index.saturating_add(1));
+            }
+
+            return Some(index);
+        }).max();
+    }
+}
+
+impl Display for Operands {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Operands::None => Ok(()),
+            Operands::Byte(value) => write!(f, "{}", value),
+            Operands::Short(value) => write!(f, "{}", value),
+            Operands::LocalVariable(index) => write!(f, "{}", index),
+            Operands::ConstantPoolIndex(index) => write!(f, "#{}", index),
+            Operands::Branch(branch) => write!(f, "{:+}", branch),
+            Operands::Iinc { index, constant } => write!(f, "{} {}", index, constant),
+            Operands::InvokeInterface { index, count } => write!(f, "#{} {}", index, count),
+            Operands::MultiANewArray { index, dimensions } => write!(f, "#{} {}", index, dimensions),
+            Operands::NewArray(atype) => write!(f, "{}", atype),
+            Operands::TableSwitch { default, low, high, offsets } => {
+                write!(f, "{}..{} [", low, high)?;
+                for (i, offset) in offsets.iter().enumerate() {
+                    write!(f, "{}: {:+}, ", i64::from(*low) + i as i64, offset)?;
+                }
+                write!(f, "default: {:+}]", default)
+            },
+            Operands::LookupSwitch { default, pairs } => {
+                write!(f, "[")?;
+                for (key, offset) in pairs {
+                    write!(f, "{}: {:+}, ", key, offset)?;
+                }
+                write!(f, "default: {:+}]", default)
+            },
+        }
+    }
+}
+
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.offset)?;
+        if self.wide {
+            write!(f, "wide ")?;
+        }
+        write!(f, "{}", self.opcode.mnemonic())?;
+        if self.operands != Operands::None {
+            write!(f, " {}", self.operands)?;
+        }
+
+        return Ok(());
+    }
+}
+
+/** https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-6.html */
+pub fn disassemble(code: &[u8]) -> Result<Vec<Instruction>, MalformedBytecode> {
+    let mut instructions = Vec::new();
+    let mut reader = Cursor::new(code);
+    while (reader.position() as usize) < code.len() {
+        let offset = reader.position() as u32;
+        instructions.push(read_instruction(&mut reader, offset).map_err(|err| match err.downcast::<MalformedBytecode>() {
+            Ok(err) => *err,
+            Err(_) => MalformedBytecode::truncated(offset),
+        })?);
+    }
+
+    return Ok(instructions);
+}
+
+/**
+ * Odmiki vseh ukazov, na katere kaže kakšen skok (goto, pogojni skoki, jsr, tableswitch, lookupswitch) ali exception handler.
+ * Uporabno za izpis oznak le tam, kjer so potrebne.
+ */
+pub fn branch_targets(code: &AttributeCode) -> Result<BTreeSet<u32>, MalformedBytecode> {
+    let mut targets = BTreeSet::new();
+    for instruction in disassemble(&code.code)? {
+        targets.extend(instruction.branch_targets());
+    }
+
+    for exception_table_entry in &code.exception_table {
+        targets.insert(exception_table_entry.handler_pc.into());
+    }
+
+    return Ok(targets);
+}
+
+/**
+ * Ciklomatska kompleksnost kode: 1 + število pogojnih skokov + število primerov v tableswitch in lookupswitch (default ni štet).
+ * Metode brez Code atributa (abstraktne in native) kompleksnosti nimajo.
+ */
+pub fn cyclomatic_complexity(code: &AttributeCode) -> Result<u32, MalformedBytecode> {
+    let mut complexity: u32 = 1;
+    for instruction in disassemble(&code.code)? {
+        complexity += match &instruction.operands {
+            Operands::TableSwitch { offsets, .. } => offsets.len() as u32,
+            Operands::LookupSwitch { pairs, .. } => pairs.len() as u32,
+            _ if instruction.is_conditional_branch() => 1,
+            _ => 0,
+        };
+    }
+
+    return Ok(complexity);
+}
+
+fn read_instruction(reader: &mut Cursor<&[u8]>, offset: u32) -> Result<Instruction, Box<dyn Error>> {
+    let opcode_byte = reader.read_u8()?;
+    let opcode = Opcode::try_from(opcode_byte).map_err(|_| MalformedBytecode { msg: format!("neznan opcode {:#0x} na odmiku {}", opcode_byte, offset) })?;
+
+    if opcode == Opcode::Wide {
+        let opcode_byte = reader.read_u8()?;
+        let opcode = Opcode::try_from(opcode_byte).map_err(|_| MalformedBytecode { msg: format!("neznan opcode {:#0x} na odmiku {}", opcode_byte, offset + 1) })?;
+        let index = reader.read_u16::<BigEndian>()?;
+        let operands = match opcode {
+            Opcode::Iload | Opcode::Lload | Opcode::Fload | Opcode::Dload | Opcode::Aload |
+            Opcode::Istore | Opcode::Lstore | Opcode::Fstore | Opcode::Dstore | Opcode::Astore | Opcode::Ret => Operands::LocalVariable(index),
+            Opcode::Iinc => Operands::Iinc { index, constant: reader.read_i16::<BigEndian>()? },
+            _ => {
+                return Err(MalformedBytecode { msg: format!("ukaz {} na odmiku {} ne more biti razširjen z wide", opcode.mnemonic(), offset) }.into());
+            }
+        };
+
+        return Ok(Instruction { offset, opcode, wide: true, operands });
+    }
+
+    let operands = match opcode {
+        Opcode::Bipush => Operands::Byte(reader.read_i8()?),
+        Opcode::Sipush => Operands::Short(reader.read_i16::<BigEndian>()?),
+        Opcode::Ldc => Operands::ConstantPoolIndex(reader.read_u8()?.into()),
+        Opcode::LdcW | Opcode::Ldc2W |
+        Opcode::Getstatic | Opcode::Putstatic | Opcode::Getfield | Opcode::Putfield |
+        Opcode::Invokevirtual | Opcode::Invokespecial | Opcode::Invokestatic |
+        Opcode::New | Opcode::Anewarray | Opcode::Checkcast | Opcode::Instanceof => Operands::ConstantPoolIndex(reader.read_u16::<BigEndian>()?),
+        Opcode::Invokedynamic => {
+            let index = reader.read_u16::<BigEndian>()?;
+            // dva bajta, ki morata biti 0
+            reader.read_u16::<BigEndian>()?;
+            Operands::ConstantPoolIndex(index)
+        },
+        Opcode::Invokeinterface => {
+            let index = reader.read_u16::<BigEndian>()?;
+            let count = reader.read_u8()?;
+            // bajt, ki mora biti 0
+            reader.read_u8()?;
+            Operands::InvokeInterface { index, count }
+        },
+        Opcode::Iload | Opcode::Lload | Opcode::Fload | Opcode::Dload | Opcode::Aload |
+        Opcode::Istore | Opcode::Lstore | Opcode::Fstore | Opcode::Dstore | Opcode::Astore | Opcode::Ret => Operands::LocalVariable(reader.read_u8()?.into()),
+        Opcode::Iinc => Operands::Iinc { index: reader.read_u8()?.into(), constant: reader.read_i8()?.into() },
+        Opcode::Ifeq | Opcode::Ifne | Opcode::Iflt | Opcode::Ifge | Opcode::Ifgt | Opcode::Ifle |
+        Opcode::IfIcmpeq | Opcode::IfIcmpne | Opcode::IfIcmplt | Opcode::IfIcmpge | Opcode::IfIcmpgt | Opcode::IfIcmple |
+        Opcode::IfAcmpeq | Opcode::IfAcmpne | Opcode::Ifnull | Opcode::Ifnonnull |
+        Opcode::Goto | Opcode::Jsr => Operands::Branch(reader.read_i16::<BigEndian>()?.into()),
+        Opcode::GotoW | Opcode::JsrW => Operands::Branch(reader.read_i32::<BigEndian>()?),
+        Opcode::Newarray => Operands::NewArray(reader.read_u8()?),
+        Opcode::Multianewarray => Operands::MultiANewArray { index: reader.read_u16::<BigEndian>()?, dimensions: reader.read_u8()? },
+        Opcode::Tableswitch => {
+            skip_switch_padding(reader, offset)?;
+            let default = reader.read_i32::<BigEndian>()?;
+            let low = reader.read_i32::<BigEndian>()?;
+            let high = reader.read_i32::<BigEndian>()?;
+            if low > high {
+                return Err(MalformedBytecode { msg: format!("tableswitch na odmiku {} ima low ({}) večji od high ({})", offset, low, high) }.into());
+            }
+
+            let mut offsets = Vec::new();
+            for _ in low..=high {
+                offsets.push(reader.read_i32::<BigEndian>()?);
+            }
+
+            Operands::TableSwitch { default, low, high, offsets }
+        },
+        Opcode::Lookupswitch => {
+            skip_switch_padding(reader, offset)?;
+            let default = reader.read_i32::<BigEndian>()?;
+            let npairs = reader.read_i32::<BigEndian>()?;
+            if npairs < 0 {
+                return Err(MalformedBytecode { msg: format!("lookupswitch na odmiku {} ima negativen npairs ({})", offset, npairs) }.into());
+            }
+
+            let mut pairs = Vec::new();
+            for _ in 0..npairs {
+                pairs.push((reader.read_i32::<BigEndian>()?, reader.read_i32::<BigEndian>()?));
+            }
+
+            Operands::LookupSwitch { default, pairs }
+        },
+        _ => Operands::None,
+    };
+
+    return Ok(Instruction { offset, opcode, wide: false, operands });
+}
+
+/** Število bajtov poravnave (0 do 3) za opcode tableswitch oz. lookupswitch na danem odmiku. Operandi se začnejo na odmiku, ki je večkratnik 4. */
+pub fn switch_padding_len(offset: u32) -> u32 {
+    return (4 - (offset + 1) % 4) % 4;
+}
+
+fn skip_switch_padding(reader: &mut Cursor<&[u8]>, offset: u32) -> Result<(), Box<dyn Error>> {
+    let padding = switch_padding_len(offset);
+    let mut padding_bytes = [0; 3];
+    reader.read_exact(&mut padding_bytes[0..padding as usize])?;
+
+    return Ok(());
+}
+
+/** Ukaz z operandom, razrešenim iz constant poola (npr. `checkcast java/util/List` namesto `checkcast #23`). */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedInstruction {
+    pub instruction: Instruction,
+    /** None, če ukaz nima operanda, ki bi ga bilo mogoče razrešiti, ali če indeks ne kaže na pričakovan vnos. */
+    pub resolved_operand: Option<String>,
+}
+
+impl Display for ResolvedInstruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.resolved_operand {
+            Some(resolved_operand) => write!(f, "{}: {} {}", self.instruction.offset, self.instruction.opcode.mnemonic(), resolved_operand),
+            None => write!(f, "{}", self.instruction),
+        }
+    }
+}
+
+/**
+ * Enako kot disassemble, le da so operandi ukazov, ki sprejmejo razred (new, anewarray, checkcast, instanceof, multianewarray)
+ * ali referenco (getstatic, putstatic, getfield, putfield, invoke*) razrešeni v imena, string konstante pri ldc in ldc_w
+ * pa izpisane v narekovajih.
+ */
+pub fn disassemble_resolved(code: &[u8], class_file: &ClassFile) -> Result<Vec<ResolvedInstruction>, MalformedBytecode> {
+    return Ok(disassemble(code)?.into_iter().map(|instruction| {
+        let resolved_operand = resolve_operand(&instruction, class_file);
+        ResolvedInstruction { instruction, resolved_operand }
+    }).collect());
+}
+
+fn resolve_operand(instruction: &Instruction, class_file: &ClassFile) -> Option<String> {
+    match (instruction.opcode, &instruction.operands) {
+        // pri anewarray in multianewarray je ime lahko tudi deskriptor tabele (npr. [Ljava/lang/String;)
+        (Opcode::New | Opcode::Anewarray | Opcode::Checkcast | Opcode::Instanceof, Operands::ConstantPoolIndex(index)) => {
+            return class_file.get_class_name(*index).map(String::from);
+        },
+        (Opcode::Multianewarray, Operands::MultiANewArray { index, dimensions }) => {
+            return class_file.get_class_name(*index).map(|name| format!("{} {}", name, dimensions));
+        },
+        (Opcode::Getstatic | Opcode::Putstatic | Opcode::Getfield | Opcode::Putfield |
+         Opcode::Invokevirtual | Opcode::Invokespecial | Opcode::Invokestatic, Operands::ConstantPoolIndex(index)) => {
+            return class_file.resolve_ref(*index).map(|member_ref| member_ref.to_string());
+        },
+        (Opcode::Invokeinterface, Operands::InvokeInterface { index, count }) => {
+            return class_file.resolve_ref(*index).map(|member_ref| format!("{} {}", member_ref, count));
+        },
+        (Opcode::Ldc | Opcode::LdcW, Operands::ConstantPoolIndex(index)) => {
+            return class_file.get_string(*index).map(|value| format!("{:?}", value));
+        },
+        (Opcode::Invokedynamic, Operands::ConstantPoolIndex(index)) => {
+            let cp_invoke_dynamic = match class_file.get_constant(*index)? {
+                CpInfo::InvokeDynamic(cp_invoke_dynamic) => cp_invoke_dynamic,
+                _ => return None,
+            };
+            let (name, descriptor) = class_file.get_name_and_type(cp_invoke_dynamic.name_and_type_index)?;
+
+            return Some(format!("#{}:{}:{}", cp_invoke_dynamic.bootstrap_method_attr_index, name, descriptor));
+        },
+        _ => None,
+    }
+}
+
+/** String literal, ki ga metoda naloži z ldc ali ldc_w. */
+#[derive(Debug, Clone)]
+pub struct StringLiteral<'a> {
+    pub method: &'a MethodInfo,
+    /** Odmik ukaza ldc oz. ldc_w v kodi metode */
+    pub offset: u32,
+    pub value: &'a str,
+}
+
+/**
+ * Vsi string literali (CpString), ki jih naložijo ukazi ldc in ldc_w v metodah classa, v vrstnem redu metod in ukazov.
+ * Za razliko od pregleda constant poola ne vključuje imen in deskriptorjev. Če je deduplicate_per_method true, je vsaka
+ * vrednost za posamezno metodo vključena le ob prvi pojavitvi.
+ */
+pub fn string_literals(class_file: &ClassFile, deduplicate_per_method: bool) -> Result<Vec<StringLiteral<'_>>, MalformedBytecode> {
+    let mut literals = Vec::new();
+    for method in &class_file.methods {
+        let Some(code) = method.code() else {
+            continue;
+        };
+
+        let mut seen_values = BTreeSet::new();
+        for instruction in disassemble(&code.code)? {
+            let (Opcode::Ldc | Opcode::LdcW, Operands::ConstantPoolIndex(index)) = (instruction.opcode, &instruction.operands) else {
+                continue;
+            };
+            let Some(value) = class_file.get_string(*index) else {
+                continue;
+            };
+
+            if deduplicate_per_method && !seen_values.insert(value) {
+                continue;
+            }
+
+            literals.push(StringLiteral { method, offset: instruction.offset, value });
+        }
+    }
+
+    return Ok(literals);
+}
+
+/** Razrešen dostop do polja z ukazom getstatic, putstatic, getfield ali putfield. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldAccess<'a> {
+    pub field_ref: MemberRef<'a>,
+    /** Tip polja, razčlenjen iz deskriptorja reference */
+    pub field_type: FieldType,
+    /** Ali ukaz dostopa do statičnega polja (getstatic, putstatic) */
+    pub is_static: bool,
+}
+
+impl Instruction {
+    /**
+     * Lastnik, ime in tip polja, do katerega dostopa ukaz getstatic, putstatic, getfield ali putfield. Vrne None pri
+     * ostalih ukazih, če indeks ne kaže na CpFieldRef ali če deskriptorja ni mogoče razčleniti.
+     */
+    pub fn field_access<'a>(&self, class_file: &'a ClassFile) -> Option<FieldAccess<'a>> {
+        let (Opcode::Getstatic | Opcode::Putstatic | Opcode::Getfield | Opcode::Putfield, Operands::ConstantPoolIndex(index)) = (self.opcode, &self.operands) else {
+            return None;
+        };
+        if !matches!(class_file.get_constant(*index)?, CpInfo::FieldRef(_)) {
+            return None;
+        }
+
+        let field_ref = class_file.resolve_ref(*index)?;
+        let field_type = parse_field_descriptor(field_ref.descriptor)?;
+
+        let is_static = matches!(self.opcode, Opcode::Getstatic | Opcode::Putstatic);
+
+        return Some(FieldAccess { field_ref, field_type, is_static });
+    }
+}
+
+/** Lambda ali referenca na metodo, ki jo ustvari invokedynamic z bootstrap metodo LambdaMetafactory.metafactory oz. altMetafactory. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct LambdaInfo<'a> {
+    /** Funkcijski vmesnik v internal form, npr. `java/util/function/Supplier` */
+    pub functional_interface: String,
+    /** Ime metode funkcijskega vmesnika, npr. `get` */
+    pub interface_method_name: &'a str,
+    /** Deskriptor metode funkcijskega vmesnika po brisanju tipov, npr. `()Ljava/lang/Object;` */
+    pub interface_method_descriptor: &'a str,
+    /** Deskriptor metode funkcijskega vmesnika z dejanskimi tipi, npr. `()Ljava/lang/String;` */
+    pub instantiated_method_descriptor: &'a str,
+    /** Metoda, ki jo lambda kliče, npr. `invokestatic p/Sample.lambda$main$0:()V` ali `invokevirtual java/lang/String.length:()I` */
+    pub implementation: ResolvedMethodHandle<'a>,
+    /** Deskriptor invokedynamic, parametri so zajete vrednosti, npr. `(Lp/Sample;)Ljava/util/function/Supplier;` */
+    pub factory_descriptor: &'a str,
+}
+
+impl Instruction {
+    /**
+     * Podatki o lambdi, če je ukaz invokedynamic, katerega bootstrap metoda je LambdaMetafactory.metafactory ali
+     * altMetafactory. Vrne None pri ostalih ukazih in bootstrap metodah ter če indeksov ni mogoče razrešiti.
+     */
+    pub fn lambda_info<'a>(&self, class_file: &'a ClassFile) -> Option<LambdaInfo<'a>> {
+        let (Opcode::Invokedynamic, Operands::ConstantPoolIndex(index)) = (self.opcode, &self.operands) else {
+            return None;
+        };
+        let CpInfo::InvokeDynamic(cp_invoke_dynamic) = class_file.get_constant(*index)? else {
+            return None;
+        };
+
+        let bootstrap_method = class_file.attributes.iter().find_map(|attribute| match attribute {
+            AttributeInfo::BootstrapMethods(bootstrap_methods) => Some(bootstrap_methods),
+            _ => None,
+        })?.bootstrap_methods.get(usize::from(cp_invoke_dynamic.bootstrap_method_attr_index))?;
+
+        let bootstrap_method_handle = class_file.resolve_method_handle(bootstrap_method.bootstrap_method_ref)?;
+        if bootstrap_method_handle.reference_kind != MethodHandleReferenceKind::InvokeStatic
+            || bootstrap_method_handle.reference.class_name != LAMBDA_METAFACTORY
+            || !matches!(bootstrap_method_handle.reference.name, "metafactory" | "altMetafactory") {
+            return None;
+        }
+
+        // metafactory in altMetafactory imata prve tri statične argumente enake
+        let [interface_method_type, implementation, instantiated_method_type, ..] = bootstrap_method.bootstrap_arguments.as_slice() else {
+            return None;
+        };
+
+        let (interface_method_name, factory_descriptor) = class_file.get_name_and_type(cp_invoke_dynamic.name_and_type_index)?;
+        let Some(FieldType::Object(functional_interface)) = parse_method_descriptor(factory_descriptor)?.return_type else {
+            return None;
+        };
+
+        return Some(LambdaInfo {
+            functional_interface,
+            interface_method_name,
+            interface_method_descriptor: method_type_descriptor(class_file, *interface_method_type)?,
+            instantiated_method_descriptor: method_type_descriptor(class_file, *instantiated_method_type)?,
+            implementation: class_file.resolve_method_handle(*implementation)?,
+            factory_descriptor,
+        });
+    }
+}
+
+fn method_type_descriptor(class_file: &ClassFile, index: u16) -> Option<&str> {
+    match class_file.get_constant(index)? {
+        CpInfo::MethodType(cp_method_type) => class_file.get_utf8(cp_method_type.descriptor_index),
+        _ => None,
+    }
+}
+
+impl MethodInfo {
+    /**
+     * Izpis kode metode, podoben `javap -c`: odmiki ukazov, oznake (L0, L1, ...) na ciljih skokov in exception handlerjev,
+     * razrešeni operandi ter številke vrstic iz LineNumberTable. Na koncu je izpisana še tabela izjem. Vrne None za metode
+     * brez Code atributa in če bytecoda ni mogoče razčleniti.
+     */
+    pub fn disassemble_listing(&self, class_file: &ClassFile) -> Option<String> {
+        let code = self.code()?;
+        let instructions = disassemble(&code.code).ok()?;
+
+        let mut targets: BTreeSet<u32> = instructions.iter().flat_map(|instruction| instruction.branch_targets()).collect();
+        targets.extend(code.exception_table.iter().map(|entry| u32::from(entry.handler_pc)));
+        let labels: BTreeMap<u32, usize> = targets.into_iter().enumerate().map(|(i, offset)| (offset, i)).collect();
+        let label = |offset: u32| labels.get(&offset).map(|i| format!("L{}", i)).unwrap_or_else(|| offset.to_string());
+
+        let mut line_numbers = BTreeMap::new();
+        for attribute in &code.attributes {
+            if let AttributeInfo::LineNumberTable(line_number_table) = attribute {
+                line_numbers.extend(line_number_table.line_number_table.iter().map(|entry| (u32::from(entry.start_pc), entry.line_number)));
+            }
+        }
+
+        let mut listing = String::new();
+        for instruction in &instructions {
+            if let Some(line_number) = line_numbers.get(&instruction.offset) {
+                listing.push_str(&format!("  // vrstica {}\n", line_number));
+            }
+            if labels.contains_key(&instruction.offset) {
+                listing.push_str(&format!("{}:\n", label(instruction.offset)));
+            }
+
+            listing.push_str(&format!("  {:>5}: {}{}", instruction.offset, if instruction.wide { "wide " } else { "" }, instruction.opcode.mnemonic()));
+            let operands = match &instruction.operands {
+                Operands::None => None,
+                Operands::Branch(branch) => Some(label(instruction.offset.wrapping_add_signed(*branch))),
+                Operands::TableSwitch { default, low, offsets, .. } => {
+                    let mut cases: Vec<String> = offsets.iter().enumerate()
+                        .map(|(i, offset)| format!("{}: {}", i64::from(*low) + i as i64, label(instruction.offset.wrapping_add_signed(*offset))))
+                        .collect();
+                    cases.push(format!("default: {}", label(instruction.offset.wrapping_add_signed(*default))));
+                    Some(format!("[{}]", cases.join(", ")))
+                },
+                Operands::LookupSwitch { default, pairs } => {
+                    let mut cases: Vec<String> = pairs.iter()
+                        .map(|(key, offset)| format!("{}: {}", key, label(instruction.offset.wrapping_add_signed(*offset))))
+                        .collect();
+                    cases.push(format!("default: {}", label(instruction.offset.wrapping_add_signed(*default))));
+                    Some(format!("[{}]", cases.join(", ")))
+                },
+                operands => Some(resolve_operand(instruction, class_file).unwrap_or_else(|| operands.to_string())),
+            };
+            if let Some(operands) = operands {
+                listing.push_str(&format!(" {}", operands));
+            }
+            listing.push('\n');
+        }
+
+        if !code.exception_table.is_empty() {
+            listing.push_str("  izjeme:\n");
+        }
+        for entry in &code.exception_table {
+            let catch_type = code.catch_type_name(entry, class_file).ok().flatten().unwrap_or("any");
+            listing.push_str(&format!("    {} {} {} {}\n", label(entry.start_pc.into()), label(entry.end_pc.into()), label(entry.handler_pc.into()), catch_type));
+        }
+
+        return Some(listing);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixture::{u16s, ClassBuilder}, parser::ClassFile};
+
+    use super::*;
+
+    /** Statična metoda m(I)V z dano kodo */
+    fn class_with_code(code: &[u8]) -> ClassFile {
+        return class_with_code_in(ClassBuilder::new("A"), code);
+    }
+
+    /** Kot class_with_code, le da koda lahko uporablja vnose, ki so že dodani v builder. */
+    fn class_with_code_in(mut builder: ClassBuilder, code: &[u8]) -> ClassFile {
+        let code = builder.code(2, 1, code, &[], &[]);
+        builder.method(0x0009, "m", "(I)V", &[code]);
+
+        return builder.parse();
+    }
+
+    /** Dva pogojna skoka, tableswitch z dvema primeroma in lookupswitch z enim, vsi skočijo na return na odmiku 52. */
+    const BRANCHING_CODE: &[u8] = &[
+        0x1a, 0x99, 0x00, 51,
+        0x1a, 0x9a, 0x00, 47,
+        0x1a, 0xaa, 0x00, 0x00, 0, 0, 0, 43, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 43, 0, 0, 0, 43,
+        0x1a, 0xab, 0x00, 0x00, 0, 0, 0, 19, 0, 0, 0, 1, 0, 0, 0, 7, 0, 0, 0, 19,
+        0xb1,
+    ];
+
+    #[test]
+    fn cyclomatic_complexity_counts_branches_and_switch_cases() {
+        let class_file = class_with_code(BRANCHING_CODE);
+
+        let code = class_file.methods[0].code().unwrap();
+
+        assert_eq!(branch_targets(code).unwrap(), BTreeSet::from([52]));
+        assert_eq!(cyclomatic_complexity(code).unwrap(), 6);
+        assert_eq!(cyclomatic_complexity(class_with_code(&[0xb1]).methods[0].code().unwrap()).unwrap(), 1);
+    }
+
+    #[test]
+    fn cyclomatic_complexity_of_malformed_code_is_error() {
+        // ifeq brez drugega bajta odmika
+        let class_file = class_with_code(&[0x1a, 0x99, 0x00]);
+
+        assert!(cyclomatic_complexity(class_file.methods[0].code().unwrap()).is_err());
+    }
+
+    #[test]
+    fn switch_padding_aligns_operands_to_four_bytes() {
+        let padding: Vec<u32> = (0..8).map(switch_padding_len).collect();
+
+        assert_eq!(padding, [3, 2, 1, 0, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn disassemble_listing_labels_targets_and_lines() {
+        let mut builder = ClassBuilder::new("A");
+        let field_ref = builder.field_ref("p/B", "x", "I");
+        let exception = builder.class("java/io/IOException");
+        let [field_ref_high, field_ref_low] = field_ref.to_be_bytes();
+        let line_number_table = builder.attribute("LineNumberTable", &u16s(&[2, 0, 10, 4, 11]));
+        // iload_0, ifeq 8, getstatic p/B.x, pop, return
+        let code = builder.code(1, 1, &[0x1a, 0x99, 0x00, 0x07, 0xb2, field_ref_high, field_ref_low, 0x57, 0xb1], &[[0, 8, 8, exception]], &[line_number_table]);
+        builder.method(0x0009, "m", "(I)V", &[code]);
+        builder.method(0x0401, "n", "()V", &[]);
+        let class_file = builder.parse();
+
+        assert_eq!(class_file.methods[0].disassemble_listing(&class_file).unwrap(), concat!(
+            "  // vrstica 10\n",
+            "      0: iload_0\n",
+            "      1: ifeq L0\n",
+            "  // vrstica 11\n",
+            "      4: getstatic p/B.x:I\n",
+            "      7: pop\n",
+            "L0:\n",
+            "      8: return\n",
+            "  izjeme:\n",
+            "    0 L0 L0 java/io/IOException\n",
+        ));
+        assert_eq!(class_file.methods[1].disassemble_listing(&class_file), None);
+    }
+
+    #[test]
+    fn disassemble_listing_of_malformed_code_is_none() {
+        let class_file = class_with_code(&[0x1a, 0x99, 0x00]);
+
+        assert_eq!(class_file.methods[0].disassemble_listing(&class_file), None);
+    }
+
+    /** Razred z metodo m, ki z invokedynamic ustvari lambdo tipa Supplier prek bootstrap metode owner.name. */
+    fn class_with_invokedynamic(bootstrap_owner: &str, bootstrap_name: &str) -> ClassFile {
+        let mut builder = ClassBuilder::new("p/Sample");
+        let bootstrap_ref = builder.method_ref(bootstrap_owner, bootstrap_name, "(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;Ljava/lang/invoke/MethodType;Ljava/lang/invoke/MethodType;Ljava/lang/invoke/MethodHandle;Ljava/lang/invoke/MethodType;)Ljava/lang/invoke/CallSite;");
+        let bootstrap_handle = builder.entry(15, &[&[6][..], &bootstrap_ref.to_be_bytes()].concat());
+        let erased_descriptor = builder.utf8("()Ljava/lang/Object;");
+        let erased_type = builder.entry(16, &erased_descriptor.to_be_bytes());
+        let implementation_ref = builder.method_ref("p/Sample", "lambda$m$0", "()Ljava/lang/String;");
+        let implementation_handle = builder.entry(15, &[&[6][..], &implementation_ref.to_be_bytes()].concat());
+        let instantiated_descriptor = builder.utf8("()Ljava/lang/String;");
+        let instantiated_type = builder.entry(16, &instantiated_descriptor.to_be_bytes());
+        let name_and_type = builder.name_and_type("get", "()Ljava/util/function/Supplier;");
+        let invoke_dynamic = builder.entry(18, &u16s(&[0, name_and_type]));
+
+        let [invoke_dynamic_high, invoke_dynamic_low] = invoke_dynamic.to_be_bytes();
+        // invokedynamic, areturn
+        let code = builder.code(1, 0, &[0xba, invoke_dynamic_high, invoke_dynamic_low, 0x00, 0x00, 0xb0], &[], &[]);
+        builder.method(0x0009, "m", "()Ljava/util/function/Supplier;", &[code]);
+        let bootstrap_methods = builder.attribute("BootstrapMethods", &u16s(&[1, bootstrap_handle, 3, erased_type, implementation_handle, instantiated_type]));
+        builder.class_attribute(bootstrap_methods);
+
+        return builder.parse();
+    }
+
+    #[test]
+    fn lambda_info_resolves_metafactory_arguments() {
+        let class_file = class_with_invokedynamic(LAMBDA_METAFACTORY, "metafactory");
+        let instructions = disassemble(&class_file.methods[0].code().unwrap().code).unwrap();
+
+        let lambda_info = instructions[0].lambda_info(&class_file).unwrap();
+
+        assert_eq!(lambda_info.functional_interface, "java/util/function/Supplier");
+        assert_eq!(lambda_info.interface_method_name, "get");
+        assert_eq!(lambda_info.interface_method_descriptor, "()Ljava/lang/Object;");
+        assert_eq!(lambda_info.instantiated_method_descriptor, "()Ljava/lang/String;");
+        assert_eq!(lambda_info.implementation.to_string(), "invokestatic p/Sample.lambda$m$0:()Ljava/lang/String;");
+        assert_eq!(lambda_info.factory_descriptor, "()Ljava/util/function/Supplier;");
+        assert_eq!(instructions[1].lambda_info(&class_file), None);
+    }
+
+    #[test]
+    fn lambda_info_ignores_other_bootstrap_methods() {
+        let alt_metafactory = class_with_invokedynamic(LAMBDA_METAFACTORY, "altMetafactory");
+        let string_concat = class_with_invokedynamic("java/lang/invoke/StringConcatFactory", "makeConcatWithConstants");
+
+        let is_lambda = |class_file: &ClassFile| disassemble(&class_file.methods[0].code().unwrap().code).unwrap()[0].lambda_info(class_file).is_some();
+
+        assert!(is_lambda(&alt_metafactory));
+        assert!(!is_lambda(&string_concat));
+    }
+
+    #[test]
+    fn max_local_slot_used_counts_second_slot_wide_and_iinc() {
+        let max_local_slot_used = |code: &[u8]| class_with_code(code).methods[0].code().unwrap().max_local_slot_used();
+
+        // iload_0, return
+        assert_eq!(max_local_slot_used(&[0x1a, 0xb1]), Some(0));
+        // lconst_0, lstore_2, return: long zaseda mesti 2 in 3
+        assert_eq!(max_local_slot_used(&[0x09, 0x41, 0xb1]), Some(3));
+        // dload 5, pop2, return
+        assert_eq!(max_local_slot_used(&[0x18, 0x05, 0x58, 0xb1]), Some(6));
+        // iload_1, iinc 7 1, return
+        assert_eq!(max_local_slot_used(&[0x1b, 0x84, 0x07, 0x01, 0xb1]), Some(7));
+        // wide iload 300, wide iinc 400 1, return
+        assert_eq!(max_local_slot_used(&[0xc4, 0x15, 0x01, 0x2c, 0xc4, 0x84, 0x01, 0x90, 0x00, 0x01, 0xb1]), Some(400));
+        // lconst_0, wide lstore 300, return
+        assert_eq!(max_local_slot_used(&[0x09, 0xc4, 0x37, 0x01, 0x2c, 0xb1]), Some(301));
+        // wide dload 65535 ne preseže u16
+        assert_eq!(max_local_slot_used(&[0xc4, 0x18, 0xff, 0xff, 0x58, 0xb1]), Some(u16::MAX));
+        // iconst_0, pop, return
+        assert_eq!(max_local_slot_used(&[0x03, 0x57, 0xb1]), None);
+        assert_eq!(max_local_slot_used(&[0x1a, 0x99, 0x00]), None);
+    }
+
+    #[test]
+    fn field_access_resolves_owner_name_type_and_static() {
+        let mut builder = ClassBuilder::new("p/A");
+        let [count_high, count_low] = builder.field_ref("p/A", "count", "I").to_be_bytes();
+        let [names_high, names_low] = builder.field_ref("p/B", "names", "[Ljava/lang/String;").to_be_bytes();
+        let [method_high, method_low] = builder.method_ref("p/A", "f", "()V").to_be_bytes();
+        let [broken_high, broken_low] = builder.field_ref("p/A", "broken", "Q").to_be_bytes();
+        let code = builder.code(3, 1, &[
+            // getstatic p/B.names, putstatic p/B.names
+            0xb2, names_high, names_low, 0xb3, names_high, names_low,
+            // aload_0, aload_0, getfield p/A.count, putfield p/A.count
+            0x2a, 0x2a, 0xb4, count_high, count_low, 0xb5, count_high, count_low,
+            // invokestatic p/A.f, getstatic z indeksom CpClass, getstatic z neveljavnim deskriptorjem, return
+            0xb8, method_high, method_low, 0xb2, 0x00, 0x01, 0xb2, broken_high, broken_low, 0xb1,
+        ], &[], &[]);
+        builder.method(0x0001, "m", "()V", &[code]);
+        let class_file = builder.parse();
+        let instructions = disassemble(&class_file.methods[0].code().unwrap().code).unwrap();
+
+        let accesses: Vec<_> = instructions.iter().map(|instruction| instruction.field_access(&class_file)).collect();
+
+        let names_type = FieldType::Array(Box::new(FieldType::Object("java/lang/String".into())));
+        let names = MemberRef { class_name: "p/B", name: "names", descriptor: "[Ljava/lang/String;" };
+        let count = MemberRef { class_name: "p/A", name: "count", descriptor: "I" };
+        assert_eq!(accesses, [
+            Some(FieldAccess { field_ref: names.clone(), field_type: names_type.clone(), is_static: true }),
+            Some(FieldAccess { field_ref: names, field_type: names_type, is_static: true }),
+            None,
+            None,
+            Some(FieldAccess { field_ref: count.clone(), field_type: FieldType::Int, is_static: false }),
+            Some(FieldAccess { field_ref: count, field_type: FieldType::Int, is_static: false }),
+            None,
+            None,
+            None,
+            None,
+        ]);
+    }
+
+    #[test]
+    fn disassemble_resolved_names_operands() {
+        let mut builder = ClassBuilder::new("p/A");
+        // ldc ima enobajten indeks, zato sta konstanti med prvimi vnosi
+        let string = builder.string("zdravo");
+        let integer = builder.integer(42);
+        let [builder_high, builder_low] = builder.class("java/lang/StringBuilder").to_be_bytes();
+        let [append_high, append_low] = builder.method_ref("java/lang/StringBuilder", "append", "(Ljava/lang/String;)Ljava/lang/StringBuilder;").to_be_bytes();
+        let [count_high, count_low] = builder.field_ref("p/A", "count", "I").to_be_bytes();
+        let code = [
+            // new, ldc "zdravo", invokevirtual, pop, ldc 42, pop, aload_0, getfield, pop, return
+            0xbb, builder_high, builder_low, 0x12, string as u8, 0xb6, append_high, append_low, 0x57,
+            0x12, integer as u8, 0x57, 0x2a, 0xb4, count_high, count_low, 0x57, 0xb1,
+        ];
+        let class_file = class_with_code_in(builder, &code);
+
+        let resolved: Vec<_> = disassemble_resolved(&class_file.methods[0].code().unwrap().code, &class_file).unwrap()
+            .iter().map(ResolvedInstruction::to_string).collect();
+
+        assert_eq!(resolved, [
+            "0: new java/lang/StringBuilder",
+            "3: ldc \"zdravo\"",
+            "5: invokevirtual java/lang/StringBuilder.append:(Ljava/lang/String;)Ljava/lang/StringBuilder;",
+            "8: pop",
+            format!("9: ldc #{}", integer).as_str(),
+            "11: pop",
+            "12: aload_0",
+            "13: getfield p/A.count:I",
+            "16: pop",
+            "17: return",
+        ]);
+    }
+
+    #[test]
+    fn string_literals_with_and_without_deduplication() {
+        let mut builder = ClassBuilder::new("p/A");
+        let a = builder.string("a") as u8;
+        let b = builder.string("b") as u8;
+        let [a_high, a_low] = u16::from(a).to_be_bytes();
+        let integer = builder.integer(1) as u8;
+        // ldc "a", pop, ldc "b", pop, ldc_w "a", pop, ldc 1, pop, return
+        let first = builder.code(1, 0, &[0x12, a, 0x57, 0x12, b, 0x57, 0x13, a_high, a_low, 0x57, 0x12, integer, 0x57, 0xb1], &[], &[]);
+        builder.method(0x0009, "first", "()V", &[first]);
+        builder.method(0x0401, "none", "()V", &[]);
+        // ldc "a", pop, return
+        let second = builder.code(1, 0, &[0x12, a, 0x57, 0xb1], &[], &[]);
+        builder.method(0x0009, "second", "()V", &[second]);
+        let class_file = builder.parse();
+
+        let literals = |deduplicate_per_method| -> Vec<(&str, u32, &str)> {
+            return string_literals(&class_file, deduplicate_per_method).unwrap().iter()
+                .map(|literal| (class_file.get_utf8(literal.method.name_index).unwrap(), literal.offset, literal.value))
+                .collect();
+        };
+
+        assert_eq!(literals(false), [("first", 0, "a"), ("first", 3, "b"), ("first", 6, "a"), ("second", 0, "a")]);
+        assert_eq!(literals(true), [("first", 0, "a"), ("first", 3, "b"), ("second", 0, "a")]);
+    }
+}
@@ -0,0 +1,118 @@
+use core::fmt::{self, Display, Formatter};
+use alloc::{string::String, vec::Vec};
+
+use crate::parser::{AttributeInfo, ClassFile};
+
+/** Izpis classa z razrešenimi imeni namesto indeksov v constant pool. Za strojno primerjavo je še vedno na voljo Debug. */
+pub struct ResolvedDisplay<'a> {
+    class_file: &'a ClassFile,
+}
+
+impl ClassFile {
+    /** Npr. `println!("{}", class_file.resolved())` */
+    pub fn resolved(&self) -> ResolvedDisplay<'_> {
+        return ResolvedDisplay { class_file: self };
+    }
+}
+
+impl Display for ResolvedDisplay<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let class_file = self.class_file;
+        writeln!(f, "class {} (verzija {}.{}, zastavice {:#06x})",
+            class_name_or_index(class_file, class_file.this_class), class_file.major_version, class_file.minor_version, class_file.access_flags)?;
+        if class_file.super_class != 0 {
+            writeln!(f, "  extends {}", class_name_or_index(class_file, class_file.super_class))?;
+        }
+        for &interface in &class_file.interfaces {
+            writeln!(f, "  implements {}", class_name_or_index(class_file, interface))?;
+        }
+        write_attributes(f, &class_file.attributes, "  ")?;
+
+        if !class_file.fields.is_empty() {
+            writeln!(f, "  polja:")?;
+        }
+        for field in &class_file.fields {
+            writeln!(f, "    {}: {} (zastavice {:#06x})",
+                utf8_or_index(class_file, field.name_index), utf8_or_index(class_file, field.descriptor_index), field.access_flags)?;
+            write_attributes(f, &field.attributes, "      ")?;
+        }
+
+        if !class_file.methods.is_empty() {
+            writeln!(f, "  metode:")?;
+        }
+        for method in &class_file.methods {
+            writeln!(f, "    {}{} (zastavice {:#06x})",
+                utf8_or_index(class_file, method.name_index), utf8_or_index(class_file, method.descriptor_index), method.access_flags)?;
+            write_attributes(f, &method.attributes, "      ")?;
+        }
+
+        return Ok(());
+    }
+}
+
+/** Izpiše imena atributov, pri Code atributu pa v oklepaju še imena njegovih atributov. */
+fn write_attributes(f: &mut Formatter<'_>, attributes: &[AttributeInfo], indent: &str) -> fmt::Result {
+    if attributes.is_empty() {
+        return Ok(());
+    }
+
+    let names: Vec<String> = attributes.iter().map(|attribute| match attribute {
+        AttributeInfo::Code(attribute_code) if !attribute_code.attributes.is_empty() => {
+            let code_attribute_names: Vec<&str> = attribute_code.attributes.iter().map(|attribute| attribute.name()).collect();
+            format!("{} ({})", attribute.name(), code_attribute_names.join(", "))
+        },
+        _ => attribute.name().into(),
+    }).collect();
+
+    return writeln!(f, "{}atributi: {}", indent, names.join(", "));
+}
+
+/** Indeksi, ki jih ni mogoče razrešiti, se izpišejo kot `#indeks`. */
+fn utf8_or_index(class_file: &ClassFile, index: u16) -> String {
+    return class_file.get_utf8(index).map(String::from).unwrap_or_else(|| format!("#{}", index));
+}
+
+fn class_name_or_index(class_file: &ClassFile, index: u16) -> String {
+    return class_file.get_class_name(index).map(String::from).unwrap_or_else(|| format!("#{}", index));
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::fixture::{u16s, ClassBuilder};
+
+    #[test]
+    fn resolved_display_golden_output() {
+        let mut builder = ClassBuilder::new("p/Sample");
+        let runnable = builder.class("java/lang/Runnable");
+        builder.interfaces.push(runnable);
+        builder.interfaces.push(0x7FFF);
+        let source_file = builder.utf8("Sample.java");
+        let source_file = builder.attribute("SourceFile", &u16s(&[source_file]));
+        builder.class_attribute(source_file);
+        let deprecated = builder.attribute("Deprecated", &[]);
+        builder.field(0x0002, "count", "I", &[deprecated]);
+        let line_number_table = builder.attribute("LineNumberTable", &u16s(&[1, 0, 1]));
+        let code = builder.code(0, 1, &[0xb1], &[], &[line_number_table]);
+        builder.method(0x0001, "run", "()V", &[code]);
+        builder.method(0x0401, "abstractMethod", "(I)I", &[]);
+
+        let output = builder.parse().resolved().to_string();
+
+        assert_eq!(output, "\
+class p/Sample (verzija 52.0, zastavice 0x0021)
+  extends java/lang/Object
+  implements java/lang/Runnable
+  implements #32767
+  atributi: SourceFile
+  polja:
+    count: I (zastavice 0x0002)
+      atributi: Deprecated
+  metode:
+    run()V (zastavice 0x0001)
+      atributi: Code (LineNumberTable)
+    abstractMethod(I)I (zastavice 0x0401)
+");
+    }
+}
@@ -0,0 +1,198 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{io::Cursor, parser::{parse_one, ClassFile}, util::string_to_modified_utf8};
+
+/**
+ * Ročno sestavljanje class datotek za teste. Vnosi constant poola se dodajajo sproti in vrnejo svoj indeks, atributi pa se
+ * sestavijo iz imena in že zapisane vsebine, tako da lahko test zapiše tudi neveljavne strukture.
+ */
+pub struct ClassBuilder {
+    pub minor_version: u16,
+    pub major_version: u16,
+    pub access_flags: u16,
+    pub this_class: u16,
+    pub super_class: u16,
+    pub interfaces: Vec<u16>,
+    constant_pool: Vec<u8>,
+    constant_pool_count: u16,
+    utf8_indices: BTreeMap<String, u16>,
+    fields: Vec<Vec<u8>>,
+    methods: Vec<Vec<u8>>,
+    attributes: Vec<Vec<u8>>,
+}
+
+impl ClassBuilder {
+    /** Javni razred z verzijo 52 (Java 8), ki razširja java/lang/Object. */
+    pub fn new(class_name: &str) -> ClassBuilder {
+        let mut builder = ClassBuilder {
+            minor_version: 0,
+            major_version: 52,
+            access_flags: 0x0021,
+            this_class: 0,
+            super_class: 0,
+            interfaces: Vec::new(),
+            constant_pool: Vec::new(),
+            constant_pool_count: 1,
+            utf8_indices: BTreeMap::new(),
+            fields: Vec::new(),
+            methods: Vec::new(),
+            attributes: Vec::new(),
+        };
+        builder.this_class = builder.class(class_name);
+        builder.super_class = builder.class("java/lang/Object");
+
+        return builder;
+    }
+
+    /** Doda vnos s poljubnim tagom in vsebino. */
+    pub fn entry(&mut self, tag: u8, info: &[u8]) -> u16 {
+        self.constant_pool.push(tag);
+        self.constant_pool.extend_from_slice(info);
+        self.constant_pool_count += 1;
+
+        return self.constant_pool_count - 1;
+    }
+
+    /** Enak niz vrne isti vnos. */
+    pub fn utf8(&mut self, value: &str) -> u16 {
+        if let Some(&index) = self.utf8_indices.get(value) {
+            return index;
+        }
+
+        let bytes = string_to_modified_utf8(value);
+        let index = self.entry(1, &[&(bytes.len() as u16).to_be_bytes()[..], &bytes].concat());
+        self.utf8_indices.insert(value.into(), index);
+
+        return index;
+    }
+
+    pub fn integer(&mut self, value: i32) -> u16 {
+        return self.entry(3, &value.to_be_bytes());
+    }
+
+    pub fn float(&mut self, value: f32) -> u16 {
+        return self.entry(4, &value.to_be_bytes());
+    }
+
+    /** Zasede dva indeksa, vrne prvega. */
+    pub fn long(&mut self, value: i64) -> u16 {
+        let index = self.entry(5, &value.to_be_bytes());
+        self.constant_pool_count += 1;
+
+        return index;
+    }
+
+    /** Zasede dva indeksa, vrne prvega. */
+    pub fn double(&mut self, value: f64) -> u16 {
+        let index = self.entry(6, &value.to_be_bytes());
+        self.constant_pool_count += 1;
+
+        return index;
+    }
+
+    pub fn class(&mut self, name: &str) -> u16 {
+        let name_index = self.utf8(name);
+        return self.entry(7, &name_index.to_be_bytes());
+    }
+
+    pub fn string(&mut self, value: &str) -> u16 {
+        let string_index = self.utf8(value);
+        return self.entry(8, &string_index.to_be_bytes());
+    }
+
+    pub fn name_and_type(&mut self, name: &str, descriptor: &str) -> u16 {
+        let name_index = self.utf8(name);
+        let descriptor_index = self.utf8(descriptor);
+        return self.entry(12, &u16s(&[name_index, descriptor_index]));
+    }
+
+    pub fn field_ref(&mut self, class_name: &str, name: &str, descriptor: &str) -> u16 {
+        let class_index = self.class(class_name);
+        let name_and_type_index = self.name_and_type(name, descriptor);
+        return self.entry(9, &u16s(&[class_index, name_and_type_index]));
+    }
+
+    pub fn method_ref(&mut self, class_name: &str, name: &str, descriptor: &str) -> u16 {
+        let class_index = self.class(class_name);
+        let name_and_type_index = self.name_and_type(name, descriptor);
+        return self.entry(10, &u16s(&[class_index, name_and_type_index]));
+    }
+
+    /** Atribut z imenom name in vsebino info, attribute_length je dolžina info. */
+    pub fn attribute(&mut self, name: &str, info: &[u8]) -> Vec<u8> {
+        let name_index = self.utf8(name);
+        return [&name_index.to_be_bytes()[..], &(info.len() as u32).to_be_bytes(), info].concat();
+    }
+
+    /** Code atribut. Vnosi exception_table so start_pc, end_pc, handler_pc in catch_type. */
+    pub fn code(&mut self, max_stack: u16, max_locals: u16, code: &[u8], exception_table: &[[u16; 4]], attributes: &[Vec<u8>]) -> Vec<u8> {
+        let mut info = u16s(&[max_stack, max_locals]);
+        info.extend_from_slice(&(code.len() as u32).to_be_bytes());
+        info.extend_from_slice(code);
+        info.extend_from_slice(&(exception_table.len() as u16).to_be_bytes());
+        for entry in exception_table {
+            info.extend_from_slice(&u16s(entry));
+        }
+        info.extend_from_slice(&attributes_table(attributes));
+
+        return self.attribute("Code", &info);
+    }
+
+    pub fn field(&mut self, access_flags: u16, name: &str, descriptor: &str, attributes: &[Vec<u8>]) {
+        let member = self.member(access_flags, name, descriptor, attributes);
+        self.fields.push(member);
+    }
+
+    pub fn method(&mut self, access_flags: u16, name: &str, descriptor: &str, attributes: &[Vec<u8>]) {
+        let member = self.member(access_flags, name, descriptor, attributes);
+        self.methods.push(member);
+    }
+
+    pub fn class_attribute(&mut self, attribute: Vec<u8>) {
+        self.attributes.push(attribute);
+    }
+
+    pub fn bytes(&self) -> Vec<u8> {
+        let mut bytes = 0xCAFEBABE_u32.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&u16s(&[self.minor_version, self.major_version, self.constant_pool_count]));
+        bytes.extend_from_slice(&self.constant_pool);
+        bytes.extend_from_slice(&u16s(&[self.access_flags, self.this_class, self.super_class, self.interfaces.len() as u16]));
+        bytes.extend_from_slice(&u16s(&self.interfaces));
+        for members in [&self.fields, &self.methods] {
+            bytes.extend_from_slice(&(members.len() as u16).to_be_bytes());
+            for member in members {
+                bytes.extend_from_slice(member);
+            }
+        }
+        bytes.extend_from_slice(&attributes_table(&self.attributes));
+
+        return bytes;
+    }
+
+    pub fn parse(&self) -> ClassFile {
+        return parse_one(&mut Cursor::new(self.bytes())).unwrap();
+    }
+
+    fn member(&mut self, access_flags: u16, name: &str, descriptor: &str, attributes: &[Vec<u8>]) -> Vec<u8> {
+        let name_index = self.utf8(name);
+        let descriptor_index = self.utf8(descriptor);
+        let mut member = u16s(&[access_flags, name_index, descriptor_index]);
+        member.extend_from_slice(&attributes_table(attributes));
+
+        return member;
+    }
+}
+
+/** Zaporedje u2 vrednosti v big endian. */
+pub fn u16s(values: &[u16]) -> Vec<u8> {
+    return values.iter().flat_map(|value| value.to_be_bytes()).collect();
+}
+
+fn attributes_table(attributes: &[Vec<u8>]) -> Vec<u8> {
+    let mut bytes = (attributes.len() as u16).to_be_bytes().to_vec();
+    for attribute in attributes {
+        bytes.extend_from_slice(attribute);
+    }
+
+    return bytes;
+}
@@ -0,0 +1,219 @@
+use alloc::{collections::{BTreeMap, BTreeSet, VecDeque}, string::{String, ToString}, vec::Vec};
+
+use crate::parser::ClassFile;
+
+/** Indeks naloženih classov (npr. iz JAR datoteke) po internem imenu, npr. `java/lang/Object`. */
+#[derive(Debug, Clone, Default)]
+pub struct SymbolIndex<'a> {
+    classes: BTreeMap<&'a str, &'a ClassFile>,
+}
+
+impl<'a> SymbolIndex<'a> {
+    /** Classi, katerih imena ni mogoče razrešiti, se izpustijo. Pri podvojenih imenih obvelja zadnji. */
+    pub fn new(class_files: impl IntoIterator<Item = &'a ClassFile>) -> SymbolIndex<'a> {
+        let mut classes = BTreeMap::new();
+        for class_file in class_files {
+            if let Some(name) = class_file.this_class_name() {
+                classes.insert(name, class_file);
+            }
+        }
+
+        return SymbolIndex { classes };
+    }
+
+    pub fn get(&self, internal_name: &str) -> Option<&'a ClassFile> {
+        return self.classes.get(internal_name).copied();
+    }
+
+    pub fn len(&self) -> usize {
+        return self.classes.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.classes.is_empty();
+    }
+
+    /**
+     * Vsi nadtipi (nadrazredi in vmesniki) classa, po plasteh od najbližjih naprej, vsak le enkrat. Tipi, ki niso v
+     * indeksu (npr. razredi iz JDK), so vključeni, njihovi nadtipi pa ne. Cikli (možni le v pokvarjenem naboru classov)
+     * ne povzročijo neskončne zanke. Če classa ni v indeksu, je rezultat prazen.
+     */
+    pub fn ancestors_of(&self, internal_name: &str) -> Vec<String> {
+        let mut ancestors = Vec::new();
+        let mut visited: BTreeSet<&str> = BTreeSet::new();
+        let mut queue: VecDeque<&ClassFile> = VecDeque::new();
+
+        let Some(class_file) = self.get(internal_name) else {
+            return ancestors;
+        };
+        visited.insert(internal_name);
+        queue.push_back(class_file);
+
+        while let Some(class_file) = queue.pop_front() {
+            let super_class = class_file.super_class_name();
+            let interfaces = class_file.interfaces.iter().filter_map(|&interface| class_file.get_class_name(interface));
+
+            for name in super_class.into_iter().chain(interfaces) {
+                if !visited.insert(name) {
+                    continue;
+                }
+
+                ancestors.push(name.to_string());
+                if let Some(ancestor) = self.get(name) {
+                    queue.push_back(ancestor);
+                }
+            }
+        }
+
+        return ancestors;
+    }
+}
+
+/** Class, ki se v naboru (npr. v JAR datoteki na več poteh) pojavi večkrat. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateClass {
+    pub name: String,
+    /** Število pojavitev, vsaj 2 */
+    pub count: usize,
+    /** Vse pojavitve so enake (enak constant pool, člani in atributi), torej gre za pravi dvojnik in ne za konflikt verzij */
+    pub identical: bool,
+    /**
+     * Polja in metode (`ime:deskriptor`), ki jih nimajo vse pojavitve. Prazno je tudi, kadar se pojavitve razlikujejo
+     * le v kodi ali atributih.
+     */
+    pub differing_members: BTreeSet<String>,
+}
+
+/** Classi, ki se v naboru pojavijo večkrat, urejeni po imenu. Classi, katerih imena ni mogoče razrešiti, se izpustijo. */
+pub fn find_duplicate_classes<'a>(class_files: impl IntoIterator<Item = &'a ClassFile>) -> Vec<DuplicateClass> {
+    let mut by_name: BTreeMap<&str, Vec<&ClassFile>> = BTreeMap::new();
+    for class_file in class_files {
+        if let Some(name) = class_file.this_class_name() {
+            by_name.entry(name).or_default().push(class_file);
+        }
+    }
+
+    let mut duplicates = Vec::new();
+    for (name, versions) in by_name {
+        if versions.len() < 2 {
+            continue;
+        }
+
+        let member_sets: Vec<BTreeSet<String>> = versions.iter().map(|class_file| member_signatures(class_file)).collect();
+        let all_members: BTreeSet<String> = member_sets.iter().flatten().cloned().collect();
+        let differing_members = all_members.into_iter().filter(|member| !member_sets.iter().all(|members| members.contains(member))).collect();
+
+        duplicates.push(DuplicateClass {
+            name: name.to_string(),
+            count: versions.len(),
+            identical: versions.iter().all(|class_file| *class_file == versions[0]),
+            differing_members,
+        });
+    }
+
+    return duplicates;
+}
+
+fn member_signatures(class_file: &ClassFile) -> BTreeSet<String> {
+    let fields = class_file.fields.iter().map(|field| (field.name_index, field.descriptor_index));
+    let methods = class_file.methods.iter().map(|method| (method.name_index, method.descriptor_index));
+
+    return fields.chain(methods).map(|(name_index, descriptor_index)| {
+        format!("{}:{}", class_file.get_utf8(name_index).unwrap_or("?"), class_file.get_utf8(descriptor_index).unwrap_or("?"))
+    }).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::ClassBuilder;
+
+    use super::*;
+
+    fn class_with_supertypes(name: &str, super_class: &str, interfaces: &[&str]) -> ClassFile {
+        let mut builder = ClassBuilder::new(name);
+        builder.super_class = builder.class(super_class);
+        for interface in interfaces {
+            let interface = builder.class(interface);
+            builder.interfaces.push(interface);
+        }
+
+        return builder.parse();
+    }
+
+    #[test]
+    fn ancestors_of_cyclic_hierarchy_terminate() {
+        let classes = [
+            class_with_supertypes("p/A", "p/B", &[]),
+            class_with_supertypes("p/B", "p/A", &["p/I"]),
+            class_with_supertypes("p/I", "java/lang/Object", &["p/I"]),
+            class_with_supertypes("p/Self", "p/Self", &[]),
+        ];
+        let index = SymbolIndex::new(&classes);
+
+        assert_eq!(index.ancestors_of("p/A"), ["p/B", "p/I", "java/lang/Object"]);
+        assert!(index.ancestors_of("p/Self").is_empty());
+        assert!(index.ancestors_of("p/Missing").is_empty());
+    }
+
+    #[test]
+    fn ancestors_of_diamond_interfaces_breadth_first() {
+        let classes = [
+            class_with_supertypes("p/C", "p/Base", &["p/L", "p/R"]),
+            class_with_supertypes("p/Base", "java/lang/Object", &["p/R"]),
+            class_with_supertypes("p/L", "java/lang/Object", &["p/Top"]),
+            class_with_supertypes("p/R", "java/lang/Object", &["p/Top", "java/io/Serializable"]),
+            class_with_supertypes("p/Top", "java/lang/Object", &[]),
+        ];
+        let index = SymbolIndex::new(&classes);
+
+        assert_eq!(index.ancestors_of("p/C"), ["p/Base", "p/L", "p/R", "java/lang/Object", "p/Top", "java/io/Serializable"]);
+    }
+
+    /** Class z eno float konstanto in metodami z danimi imeni */
+    fn class_with_methods(name: &str, constant: f32, methods: &[&str]) -> ClassFile {
+        let mut builder = ClassBuilder::new(name);
+        builder.float(constant);
+        for method in methods {
+            builder.method(0x0401, method, "()V", &[]);
+        }
+
+        return builder.parse();
+    }
+
+    #[test]
+    fn identical_duplicates_are_reported_once() {
+        // konstante se primerjajo po bajtih, zato sta enaka tudi classa s konstanto NaN
+        let classes = [
+            class_with_methods("p/B", f32::NAN, &["run"]),
+            class_with_methods("p/A", 0.0, &[]),
+            class_with_methods("p/B", f32::NAN, &["run"]),
+        ];
+
+        assert_eq!(find_duplicate_classes(&classes), [DuplicateClass {
+            name: "p/B".into(),
+            count: 2,
+            identical: true,
+            differing_members: BTreeSet::new(),
+        }]);
+    }
+
+    #[test]
+    fn differing_duplicates_list_missing_members() {
+        let classes = [
+            class_with_methods("p/B", 1.0, &["run", "stop"]),
+            class_with_methods("p/A", 1.0, &["run"]),
+            class_with_methods("p/B", 1.0, &["run", "start"]),
+            class_with_methods("p/A", 2.0, &["run"]),
+        ];
+
+        assert_eq!(find_duplicate_classes(&classes), [
+            DuplicateClass { name: "p/A".into(), count: 2, identical: false, differing_members: BTreeSet::new() },
+            DuplicateClass {
+                name: "p/B".into(),
+                count: 2,
+                identical: false,
+                differing_members: BTreeSet::from(["start:()V".into(), "stop:()V".into()]),
+            },
+        ]);
+    }
+}
@@ -0,0 +1,496 @@
+use core::fmt;
+use alloc::{string::String, vec::Vec};
+
+use crate::{parser::{AttributeCode, AttributeInfo, AttributeRecordComponentInfo, AttributeRuntimeAnnotationsEntry, AttributeRuntimeTypeAnnotationsEntry, AttributeRuntimeTypeAnnotationsEntryTargetInfo, AttributeSourceDebugExtension, ClassAccessFlagMask, ClassFile, FieldAccessFlagMask, FieldInfo, MethodAccessFlagMask, MethodInfo}, util::{modified_utf8_to_string, MalformedModifiedUtf8}};
+
+/** Prvi major_version (Java 5), ki ga ne štejemo več za zastarelega */
+const MODERN_MIN_MAJOR_VERSION: u16 = 49;
+
+/** Prvi major_version (Java 7), pri katerem mora imeti <clinit> zastavico static */
+const STATIC_CLINIT_MIN_MAJOR_VERSION: u16 = 51;
+
+/** Verzija classa. Urejenost je najprej po major, nato po minor. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClassVersion {
+    pub major: u16,
+    pub minor: u16,
+}
+
+impl ClassVersion {
+    /**
+     * Classi pred Javo 5 (major < 49). Pri njih se ne sme predpostavljati sodobne semantike zastavic, npr. zastavica
+     * ACC_SUPER pri verziji 45 z minor < 3 ne obstaja, ACC_SYNTHETIC, ACC_ENUM in ACC_ANNOTATION pa še niso definirane.
+     */
+    pub fn is_ancient(&self) -> bool {
+        return self.major < MODERN_MIN_MAJOR_VERSION;
+    }
+}
+
+impl fmt::Display for ClassVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/** Ali je anotacija dostopna med izvajanjem (RuntimeVisibleAnnotations) ali ne (RuntimeInvisibleAnnotations). */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retention {
+    Visible,
+    Invisible,
+}
+
+/** Komponenta recorda z razrešenim imenom in deskriptorjem. */
+#[derive(Debug, Clone, Copy)]
+pub struct RecordComponent<'a> {
+    pub name: &'a str,
+    pub descriptor: &'a str,
+    pub info: &'a AttributeRecordComponentInfo,
+    class_file: &'a ClassFile,
+}
+
+/** Tipska anotacija iz Code atributa z retencijo in opisom mesta, na katerega se nanaša (glej target_description). */
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeTypeAnnotation<'a> {
+    pub retention: Retention,
+    pub entry: &'a AttributeRuntimeTypeAnnotationsEntry,
+    pub target_description: String,
+}
+
+impl ClassFile {
+    /**
+     * Imena enum konstant v vrstnem redu deklaracije oz. None, če razred ni enum. Enum konstante so polja z zastavicami
+     * public, static, final in enum, katerih tip je razred sam. Sintetična polja (npr. `$VALUES`) in ostala statična polja
+     * tipa enuma (npr. `static final Color DEFAULT = RED`) niso vključena.
+     */
+    pub fn enum_constants(&self) -> Option<Vec<&str>> {
+        if self.access_flags & ClassAccessFlagMask::Enum as u16 == 0 {
+            return None;
+        }
+
+        let enum_descriptor = format!("L{};", self.this_class_name()?);
+        let constant_flags = FieldAccessFlagMask::Public as u16 | FieldAccessFlagMask::Static as u16 | FieldAccessFlagMask::Final as u16 | FieldAccessFlagMask::Enum as u16;
+
+        let mut constants = Vec::new();
+        for field in &self.fields {
+            if field.access_flags & constant_flags != constant_flags || field.access_flags & FieldAccessFlagMask::Synthetic as u16 != 0 {
+                continue;
+            }
+
+            if self.get_utf8(field.descriptor_index) != Some(enum_descriptor.as_str()) {
+                continue;
+            }
+
+            if let Some(name) = self.get_utf8(field.name_index) {
+                constants.push(name);
+            }
+        }
+
+        return Some(constants);
+    }
+
+    /** Ali je class preveden iz package-info.java (enostavno ime razreda je `package-info`). */
+    pub fn is_package_info(&self) -> bool {
+        let Some(class_name) = self.this_class_name() else {
+            return false;
+        };
+
+        return class_name.rsplit('/').next() == Some("package-info");
+    }
+
+    /** Ali je class preveden iz module-info.java (ima zastavico ACC_MODULE in Module atribut). */
+    pub fn is_module_info(&self) -> bool {
+        return self.access_flags & ClassAccessFlagMask::Module as u16 != 0
+            && self.attributes.iter().any(|attribute| matches!(attribute, AttributeInfo::Module(_)));
+    }
+
+    pub fn version(&self) -> ClassVersion {
+        return ClassVersion { major: self.major_version, minor: self.minor_version };
+    }
+
+    /**
+     * Statični inicializator (metoda `<clinit>` z deskriptorjem `()V`) oz. None, če ga class nima. Od verzije 51 naprej
+     * mora imeti tudi zastavico static, pri starejših pa se zastavice ne upoštevajo
+     * (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-2.html#jvms-2.9.2).
+     */
+    pub fn static_initializer(&self) -> Option<&MethodInfo> {
+        let requires_static = self.major_version >= STATIC_CLINIT_MIN_MAJOR_VERSION;
+
+        return self.methods.iter().find(|method| {
+            self.get_utf8(method.name_index) == Some("<clinit>")
+                && self.get_utf8(method.descriptor_index) == Some("()V")
+                && (!requires_static || method.access_flags & MethodAccessFlagMask::Static as u16 != 0)
+        });
+    }
+
+    /** Anotacije razreda iz RuntimeVisibleAnnotations in RuntimeInvisibleAnnotations. */
+    pub fn all_annotations(&self) -> impl Iterator<Item = (&AttributeRuntimeAnnotationsEntry, Retention)> {
+        return annotations_with_retention(&self.attributes);
+    }
+
+    /** Komponente recorda v vrstnem redu deklaracije oz. None, če class nima Record atributa ali imena komponent niso CpUtf8. */
+    pub fn record_components(&self) -> Option<Vec<RecordComponent<'_>>> {
+        let attribute_record = self.attributes.iter().find_map(|attribute| match attribute {
+            AttributeInfo::Record(attribute_record) => Some(attribute_record),
+            _ => None,
+        })?;
+
+        let mut components = Vec::with_capacity(attribute_record.components.len());
+        for info in &attribute_record.components {
+            components.push(RecordComponent {
+                name: self.get_utf8(info.name_index)?,
+                descriptor: self.get_utf8(info.descriptor_index)?,
+                info,
+                class_file: self,
+            });
+        }
+
+        return Some(components);
+    }
+}
+
+impl<'a> RecordComponent<'a> {
+    /** Generična signatura komponente (Signature atribut), npr. `Ljava/util/List<TT;>;`. */
+    pub fn signature(&self) -> Option<&'a str> {
+        return self.info.attributes.iter().find_map(|attribute| match attribute {
+            AttributeInfo::Signature(attribute_signature) => self.class_file.get_utf8(attribute_signature.signature_index),
+            _ => None,
+        });
+    }
+
+    pub fn visible_annotations(&self) -> &'a [AttributeRuntimeAnnotationsEntry] {
+        return self.info.attributes.iter().find_map(|attribute| match attribute {
+            AttributeInfo::RuntimeVisibleAnnotations(attribute_annotations) => Some(attribute_annotations.annotations.as_slice()),
+            _ => None,
+        }).unwrap_or(&[]);
+    }
+
+    pub fn invisible_annotations(&self) -> &'a [AttributeRuntimeAnnotationsEntry] {
+        return self.info.attributes.iter().find_map(|attribute| match attribute {
+            AttributeInfo::RuntimeInvisibleAnnotations(attribute_annotations) => Some(attribute_annotations.annotations.as_slice()),
+            _ => None,
+        }).unwrap_or(&[]);
+    }
+
+    pub fn visible_type_annotations(&self) -> &'a [AttributeRuntimeTypeAnnotationsEntry] {
+        return self.info.attributes.iter().find_map(|attribute| match attribute {
+            AttributeInfo::RuntimeVisibleTypeAnnotations(attribute_type_annotations) => Some(attribute_type_annotations.annotations.as_slice()),
+            _ => None,
+        }).unwrap_or(&[]);
+    }
+
+    pub fn invisible_type_annotations(&self) -> &'a [AttributeRuntimeTypeAnnotationsEntry] {
+        return self.info.attributes.iter().find_map(|attribute| match attribute {
+            AttributeInfo::RuntimeInvisibleTypeAnnotations(attribute_type_annotations) => Some(attribute_type_annotations.annotations.as_slice()),
+            _ => None,
+        }).unwrap_or(&[]);
+    }
+}
+
+impl FieldInfo {
+    pub fn all_annotations(&self) -> impl Iterator<Item = (&AttributeRuntimeAnnotationsEntry, Retention)> {
+        return annotations_with_retention(&self.attributes);
+    }
+}
+
+impl MethodInfo {
+    /** Anotacije metode (brez anotacij parametrov, ki so v RuntimeVisibleParameterAnnotations in RuntimeInvisibleParameterAnnotations). */
+    pub fn all_annotations(&self) -> impl Iterator<Item = (&AttributeRuntimeAnnotationsEntry, Retention)> {
+        return annotations_with_retention(&self.attributes);
+    }
+
+    /** Code atribut metode oz. None za abstraktne in native metode. */
+    pub fn code(&self) -> Option<&AttributeCode> {
+        return self.attributes.iter().find_map(|attribute| match attribute {
+            AttributeInfo::Code(code) => Some(code),
+            _ => None,
+        });
+    }
+
+    /** (max_stack, max_locals) iz Code atributa oz. None za abstraktne in native metode. */
+    pub fn frame_sizes(&self) -> Option<(u16, u16)> {
+        let code = self.code()?;
+
+        return Some((code.max_stack, code.max_locals));
+    }
+}
+
+impl AttributeCode {
+    /**
+     * Tipske anotacije iz RuntimeVisibleTypeAnnotations in RuntimeInvisibleTypeAnnotations v Code atributu (npr. na lokalnih
+     * spremenljivkah, castih in instanceof) v vrstnem redu atributov.
+     */
+    pub fn type_annotations(&self) -> Vec<CodeTypeAnnotation<'_>> {
+        return self.attributes.iter().flat_map(|attribute| {
+            let (annotations, retention) = match attribute {
+                AttributeInfo::RuntimeVisibleTypeAnnotations(attribute_type_annotations) => (attribute_type_annotations.annotations.as_slice(), Retention::Visible),
+                AttributeInfo::RuntimeInvisibleTypeAnnotations(attribute_type_annotations) => (attribute_type_annotations.annotations.as_slice(), Retention::Invisible),
+                _ => (&[][..], Retention::Visible),
+            };
+
+            return annotations.iter().map(move |entry| CodeTypeAnnotation { retention, entry, target_description: entry.target_description() });
+        }).collect();
+    }
+}
+
+impl AttributeRuntimeTypeAnnotationsEntry {
+    /**
+     * Opis mesta, na katerega se anotacija nanaša, npr. `cast (odmik 12, tipski argument 0)`
+     * (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.7.20-400).
+     */
+    pub fn target_description(&self) -> String {
+        let kind = match self.target_type {
+            0x00 => "parameter tipa razreda",
+            0x01 => "parameter tipa metode",
+            0x10 => "nadtip",
+            0x11 => "meja parametra tipa razreda",
+            0x12 => "meja parametra tipa metode",
+            0x13 => "polje",
+            0x14 => "tip rezultata",
+            0x15 => "receiver",
+            0x16 => "parameter metode",
+            0x17 => "throws",
+            0x40 => "lokalna spremenljivka",
+            0x41 => "resource spremenljivka",
+            0x42 => "catch",
+            0x43 => "instanceof",
+            0x44 => "new",
+            0x45 => "referenca na konstruktor (::new)",
+            0x46 => "referenca na metodo (::ime)",
+            0x47 => "cast",
+            0x48 => "tipski argument konstruktorja",
+            0x49 => "tipski argument metode",
+            0x4A => "tipski argument reference na konstruktor",
+            0x4B => "tipski argument reference na metodo",
+            _ => "neznano",
+        };
+
+        let details = match &self.target_info {
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::TypeParameterTarget(target) => format!("parameter {}", target.type_parameter_index),
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::SuperTypeTarget(target) if target.supertype_index == u16::MAX => "nadrazred".into(),
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::SuperTypeTarget(target) => format!("vmesnik {}", target.supertype_index),
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::TypeParameterBoundTarget(target) => format!("parameter {}, meja {}", target.type_parameter_index, target.bound_index),
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::EmptyTarget(_) => return kind.into(),
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::FormalParameterTarget(target) => format!("parameter {}", target.formal_parameter_index),
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::ThrowsTarget(target) => format!("izjema {}", target.throws_type_index),
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::LocalvarTarget(target) => {
+                let ranges: Vec<String> = target.table.iter()
+                    .map(|entry| format!("mesto {} od {} do {}", entry.index, entry.start_pc, u32::from(entry.start_pc) + u32::from(entry.length)))
+                    .collect();
+                ranges.join(", ")
+            },
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::CatchTarget(target) => format!("vnos {} v tabeli izjem", target.exception_table_index),
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::OffsetTarget(target) => format!("odmik {}", target.offset),
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::TypeArgumentTarget(target) => format!("odmik {}, tipski argument {}", target.offset, target.type_argument_index),
+        };
+
+        return format!("{} ({})", kind, details);
+    }
+}
+
+fn annotations_with_retention(attributes: &[AttributeInfo]) -> impl Iterator<Item = (&AttributeRuntimeAnnotationsEntry, Retention)> {
+    return attributes.iter().flat_map(|attribute| {
+        let (annotations, retention) = match attribute {
+            AttributeInfo::RuntimeVisibleAnnotations(attribute_annotations) => (attribute_annotations.annotations.as_slice(), Retention::Visible),
+            AttributeInfo::RuntimeInvisibleAnnotations(attribute_annotations) => (attribute_annotations.annotations.as_slice(), Retention::Invisible),
+            _ => (&[][..], Retention::Visible),
+        };
+
+        return annotations.iter().map(move |annotation| (annotation, retention));
+    });
+}
+
+impl AttributeSourceDebugExtension {
+    /** Vsebina atributa kot niz (npr. SMAP po JSR-45). debug_extension je zapisan v modified UTF-8. */
+    pub fn as_str(&self) -> Result<String, MalformedModifiedUtf8> {
+        return modified_utf8_to_string(&self.debug_extension);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::{fixture::{u16s, ClassBuilder}, util::string_to_modified_utf8};
+
+    use super::*;
+
+    #[test]
+    fn code_type_annotations_keep_retention_and_target() {
+        let mut builder = ClassBuilder::new("A");
+        let non_null = builder.utf8("LNonNull;");
+        let nullable = builder.utf8("LNullable;");
+        // cast na odmiku 1, tipski argument 0, prazna type_path
+        let cast = [&[0x47, 0x00, 0x01, 0x00, 0x00][..], &u16s(&[non_null, 0])].concat();
+        // lokalna spremenljivka na mestu 1 od 0 do 2
+        let local_variable = [&[0x40][..], &u16s(&[1, 0, 2, 1]), &[0x00], &u16s(&[nullable, 0])].concat();
+        let visible = builder.attribute("RuntimeVisibleTypeAnnotations", &[&u16s(&[1])[..], &cast].concat());
+        let invisible = builder.attribute("RuntimeInvisibleTypeAnnotations", &[&u16s(&[1])[..], &local_variable].concat());
+        let code = builder.code(1, 2, &[0x2b, 0xb0], &[], &[visible, invisible]);
+        builder.method(0x0009, "m", "(Ljava/lang/Object;)Ljava/lang/Object;", &[code]);
+        let class_file = builder.parse();
+
+        let type_annotations = class_file.methods[0].code().unwrap().type_annotations();
+
+        let summary: Vec<_> = type_annotations.iter().map(|annotation| (annotation.retention, annotation.entry.type_index, annotation.target_description.as_str())).collect();
+        assert_eq!(summary, [
+            (Retention::Visible, non_null, "cast (odmik 1, tipski argument 0)"),
+            (Retention::Invisible, nullable, "lokalna spremenljivka (mesto 1 od 0 do 2)"),
+        ]);
+    }
+
+    #[test]
+    fn package_info_and_module_info() {
+        let package_info = ClassBuilder::new("p/q/package-info").parse();
+        assert!(package_info.is_package_info());
+        assert!(!package_info.is_module_info());
+        assert!(!ClassBuilder::new("p/q/package_info").parse().is_package_info());
+
+        let mut builder = ClassBuilder::new("module-info");
+        builder.access_flags = 0x8000;
+        builder.super_class = 0;
+        let module_name = builder.utf8("m");
+        let module = builder.entry(19, &u16s(&[module_name]));
+        let module_attribute = builder.attribute("Module", &u16s(&[module, 0, 0, 0, 0, 0, 0, 0]));
+        builder.class_attribute(module_attribute);
+        let module_info = builder.parse();
+        assert!(module_info.is_module_info());
+        assert!(!module_info.is_package_info());
+
+        let mut builder = ClassBuilder::new("module-info");
+        builder.access_flags = 0x8000;
+        assert!(!builder.parse().is_module_info());
+    }
+
+    fn annotations(builder: &mut ClassBuilder, name: &str, types: &[&str]) -> Vec<u8> {
+        let mut info = u16s(&[types.len() as u16]);
+        for annotation_type in types {
+            let type_index = builder.utf8(annotation_type);
+            info.extend_from_slice(&u16s(&[type_index, 0]));
+        }
+
+        return builder.attribute(name, &info);
+    }
+
+    fn annotation_types<'a>(class_file: &'a ClassFile, annotations: impl Iterator<Item = (&'a AttributeRuntimeAnnotationsEntry, Retention)>) -> Vec<(&'a str, Retention)> {
+        return annotations.map(|(annotation, retention)| (class_file.get_utf8(annotation.type_index).unwrap(), retention)).collect();
+    }
+
+    #[test]
+    fn all_annotations_across_retentions() {
+        let mut builder = ClassBuilder::new("A");
+        let visible = annotations(&mut builder, "RuntimeVisibleAnnotations", &["LV1;", "LV2;"]);
+        let invisible = annotations(&mut builder, "RuntimeInvisibleAnnotations", &["LI;"]);
+        builder.class_attribute(invisible);
+        builder.class_attribute(visible);
+        let field_annotations = annotations(&mut builder, "RuntimeInvisibleAnnotations", &["LF;"]);
+        builder.field(0x0002, "f", "I", &[field_annotations]);
+        let method_annotations = annotations(&mut builder, "RuntimeVisibleAnnotations", &["LM;"]);
+        let parameter_annotations = builder.attribute("RuntimeVisibleParameterAnnotations", &[&[1][..], &u16s(&[0])].concat());
+        builder.method(0x0401, "m", "(I)V", &[parameter_annotations, method_annotations]);
+        builder.method(0x0401, "n", "()V", &[]);
+        let class_file = builder.parse();
+
+        assert_eq!(annotation_types(&class_file, class_file.all_annotations()), [("LI;", Retention::Invisible), ("LV1;", Retention::Visible), ("LV2;", Retention::Visible)]);
+        assert_eq!(annotation_types(&class_file, class_file.fields[0].all_annotations()), [("LF;", Retention::Invisible)]);
+        assert_eq!(annotation_types(&class_file, class_file.methods[0].all_annotations()), [("LM;", Retention::Visible)]);
+        assert_eq!(class_file.methods[1].all_annotations().count(), 0);
+    }
+
+    #[test]
+    fn ancient_class_without_acc_super() {
+        let mut builder = ClassBuilder::new("Old");
+        builder.major_version = 45;
+        builder.minor_version = 2;
+        builder.access_flags = ClassAccessFlagMask::Public as u16;
+        let class_file = builder.parse();
+
+        let version = class_file.version();
+
+        assert_eq!(version, ClassVersion { major: 45, minor: 2 });
+        assert_eq!(version.to_string(), "45.2");
+        assert!(version.is_ancient());
+        assert_eq!(class_file.access_flags & ClassAccessFlagMask::Super as u16, 0);
+    }
+
+    #[test]
+    fn class_versions_are_ordered() {
+        assert!(ClassVersion { major: 45, minor: 3 } < ClassVersion { major: 49, minor: 0 });
+        assert!(ClassVersion { major: 52, minor: 0 } < ClassVersion { major: 52, minor: 65535 });
+        assert!(!ClassVersion { major: 49, minor: 0 }.is_ancient());
+    }
+
+    #[test]
+    fn static_initializer_requires_static_since_version_51() {
+        let mut builder = ClassBuilder::new("A");
+        builder.method(0x0008, "<clinit>", "(I)V", &[]);
+        builder.method(0x0000, "<clinit>", "()V", &[]);
+        builder.method(0x0008, "<clinit>", "()V", &[]);
+        let class_file = builder.parse();
+        assert!(core::ptr::eq(class_file.static_initializer().unwrap(), &class_file.methods[2]));
+
+        let mut builder = ClassBuilder::new("A");
+        builder.major_version = 50;
+        builder.method(0x0000, "<clinit>", "()V", &[]);
+        assert!(builder.parse().static_initializer().is_some());
+
+        let mut builder = ClassBuilder::new("A");
+        builder.method(0x0000, "<clinit>", "()V", &[]);
+        assert!(builder.parse().static_initializer().is_none());
+    }
+
+    #[test]
+    fn frame_sizes_from_code() {
+        let mut builder = ClassBuilder::new("A");
+        let code = builder.code(3, 5, &[0xb1], &[], &[]);
+        builder.method(0x0009, "m", "()V", &[code]);
+        builder.method(0x0401, "n", "()V", &[]);
+        let class_file = builder.parse();
+
+        assert_eq!(class_file.methods[0].frame_sizes(), Some((3, 5)));
+        assert_eq!(class_file.methods[1].frame_sizes(), None);
+        assert!(class_file.methods[1].code().is_none());
+    }
+
+    #[test]
+    fn source_debug_extension_decodes_smap() {
+        let smap = "SMAP\nVstop.jsp\nJSP\n*S JSP\n*F\n+ 0 Vstop.jsp\nspletna/Vstop.jsp\n*L\n1#1,5:10\n*E\n// čšž\n";
+        let mut builder = ClassBuilder::new("p/Vstop_jsp");
+        let debug_extension = builder.attribute("SourceDebugExtension", &string_to_modified_utf8(smap));
+        builder.class_attribute(debug_extension);
+        let class_file = builder.parse();
+
+        let AttributeInfo::SourceDebugExtension(attribute_debug_extension) = &class_file.attributes[0] else {
+            panic!("pričakovan SourceDebugExtension");
+        };
+        assert_eq!(attribute_debug_extension.as_str().unwrap(), smap);
+    }
+
+    #[test]
+    fn source_debug_extension_with_invalid_modified_utf8_is_error() {
+        // ničelni bajt in nedokončano zaporedje nista veljaven modified UTF-8
+        for debug_extension in [vec![b'S', 0x00], vec![b'S', 0xC4]] {
+            assert!(AttributeSourceDebugExtension { debug_extension }.as_str().is_err());
+        }
+    }
+
+    #[test]
+    fn enum_constants_in_declaration_order() {
+        let mut builder = ClassBuilder::new("p/Color");
+        builder.access_flags = 0x4031;
+        builder.field(0x4019, "RED", "Lp/Color;", &[]);
+        builder.field(0x0012, "rgb", "I", &[]);
+        builder.field(0x4019, "GREEN", "Lp/Color;", &[]);
+        builder.field(0x0019, "DEFAULT", "Lp/Color;", &[]);
+        builder.field(0x101A, "$VALUES", "[Lp/Color;", &[]);
+
+        assert_eq!(builder.parse().enum_constants(), Some(vec!["RED", "GREEN"]));
+    }
+
+    #[test]
+    fn enum_constants_of_non_enum_class_is_none() {
+        let mut builder = ClassBuilder::new("p/Color");
+        builder.field(0x4019, "RED", "Lp/Color;", &[]);
+
+        assert_eq!(builder.parse().enum_constants(), None);
+    }
+}
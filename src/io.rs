@@ -0,0 +1,255 @@
+/*!
+ * Branje in pisanje bajtov, neodvisno od std. S feature `std` so tipi kar tisti iz std::io, zato parser sprejme vsak
+ * std::io::Read. Brez njega (no_std + alloc) so na voljo le Read, Cursor in Error z enakim vmesnikom, kot ga ima std::io
+ * (podobno kot v crate core2), parser pa bere iz &[u8] ali Cursor.
+ */
+
+use alloc::vec::Vec;
+use byteorder::ByteOrder;
+
+#[cfg(feature = "std")]
+pub use std::io::{Cursor, Error, ErrorKind, Read, Result};
+
+#[cfg(not(feature = "std"))]
+pub use self::core_io::{Cursor, Error, ErrorKind, Read, Result};
+
+#[cfg(not(feature = "std"))]
+mod core_io {
+    use core::{error, fmt};
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ErrorKind {
+        UnexpectedEof,
+        Interrupted,
+        Other,
+    }
+
+    #[derive(Debug)]
+    pub struct Error {
+        kind: ErrorKind,
+    }
+
+    impl Error {
+        pub fn kind(&self) -> ErrorKind {
+            return self.kind;
+        }
+    }
+
+    impl From<ErrorKind> for Error {
+        fn from(kind: ErrorKind) -> Error {
+            return Error { kind };
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.kind {
+                ErrorKind::UnexpectedEof => write!(f, "failed to fill whole buffer"),
+                ErrorKind::Interrupted => write!(f, "operation interrupted"),
+                ErrorKind::Other => write!(f, "other error"),
+            }
+        }
+    }
+
+    impl error::Error for Error {}
+
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /** Podmnožica std::io::Read, ki jo potrebuje parser. */
+    pub trait Read {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+
+        fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<()> {
+            while !buf.is_empty() {
+                match self.read(buf)? {
+                    0 => return Err(ErrorKind::UnexpectedEof.into()),
+                    bytes_read => buf = &mut buf[bytes_read..],
+                }
+            }
+
+            return Ok(());
+        }
+
+        fn by_ref(&mut self) -> &mut Self where Self: Sized {
+            return self;
+        }
+    }
+
+    impl<R: Read + ?Sized> Read for &mut R {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            return (**self).read(buf);
+        }
+    }
+
+    impl Read for &[u8] {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let bytes_read = buf.len().min(self.len());
+            let (read, rest) = self.split_at(bytes_read);
+            buf[..bytes_read].copy_from_slice(read);
+            *self = rest;
+
+            return Ok(bytes_read);
+        }
+    }
+
+    /** Enako kot std::io::Cursor: bralnik po bajtih v pomnilniku, ki si zapomni trenutni odmik. */
+    #[derive(Debug, Clone, Default)]
+    pub struct Cursor<T> {
+        inner: T,
+        position: u64,
+    }
+
+    impl<T> Cursor<T> {
+        pub fn new(inner: T) -> Cursor<T> {
+            return Cursor { inner, position: 0 };
+        }
+
+        pub fn position(&self) -> u64 {
+            return self.position;
+        }
+
+        pub fn set_position(&mut self, position: u64) {
+            self.position = position;
+        }
+
+        pub fn get_ref(&self) -> &T {
+            return &self.inner;
+        }
+
+        pub fn into_inner(self) -> T {
+            return self.inner;
+        }
+    }
+
+    impl<T: AsRef<[u8]>> Read for Cursor<T> {
+        fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+            let bytes = self.inner.as_ref();
+            let start = usize::try_from(self.position).unwrap_or(usize::MAX).min(bytes.len());
+            let bytes_read = (&bytes[start..]).read(buf)?;
+            self.position += bytes_read as u64;
+
+            return Ok(bytes_read);
+        }
+    }
+}
+
+/** Branje števil v danem vrstnem redu bajtov, enako kot byteorder::ReadBytesExt, le da deluje tudi brez std. */
+pub trait ReadBytesExt: Read {
+    fn read_u8(&mut self) -> Result<u8> {
+        let mut buf = [0; 1];
+        self.read_exact(&mut buf)?;
+        return Ok(buf[0]);
+    }
+
+    fn read_i8(&mut self) -> Result<i8> {
+        return Ok(self.read_u8()? as i8);
+    }
+
+    fn read_u16<B: ByteOrder>(&mut self) -> Result<u16> {
+        let mut buf = [0; 2];
+        self.read_exact(&mut buf)?;
+        return Ok(B::read_u16(&buf));
+    }
+
+    fn read_i16<B: ByteOrder>(&mut self) -> Result<i16> {
+        let mut buf = [0; 2];
+        self.read_exact(&mut buf)?;
+        return Ok(B::read_i16(&buf));
+    }
+
+    fn read_u32<B: ByteOrder>(&mut self) -> Result<u32> {
+        let mut buf = [0; 4];
+        self.read_exact(&mut buf)?;
+        return Ok(B::read_u32(&buf));
+    }
+
+    fn read_i32<B: ByteOrder>(&mut self) -> Result<i32> {
+        let mut buf = [0; 4];
+        self.read_exact(&mut buf)?;
+        return Ok(B::read_i32(&buf));
+    }
+}
+
+impl<R: Read + ?Sized> ReadBytesExt for R {}
+
+/** Pisanje števil v danem vrstnem redu bajtov, enako kot byteorder::WriteBytesExt za Vec<u8>. Pisanje ne more spodleteti. */
+pub trait WriteBytesExt {
+    fn write_u8(&mut self, value: u8) -> Result<()>;
+    fn write_u16<B: ByteOrder>(&mut self, value: u16) -> Result<()>;
+    fn write_u32<B: ByteOrder>(&mut self, value: u32) -> Result<()>;
+}
+
+impl WriteBytesExt for Vec<u8> {
+    fn write_u8(&mut self, value: u8) -> Result<()> {
+        self.push(value);
+        return Ok(());
+    }
+
+    fn write_u16<B: ByteOrder>(&mut self, value: u16) -> Result<()> {
+        let mut buf = [0; 2];
+        B::write_u16(&mut buf, value);
+        self.extend_from_slice(&buf);
+        return Ok(());
+    }
+
+    fn write_u32<B: ByteOrder>(&mut self, value: u32) -> Result<()> {
+        let mut buf = [0; 4];
+        B::write_u32(&mut buf, value);
+        self.extend_from_slice(&buf);
+        return Ok(());
+    }
+}
+
+/** Preskoči length bajtov in vrne, koliko jih je bilo dejansko preskočenih (manj le, če se tok prej konča). */
+pub fn skip(reader: &mut impl Read, length: u64) -> Result<u64> {
+    let mut buf = [0; 256];
+    let mut skipped = 0;
+    while skipped < length {
+        let chunk_length = usize::try_from(length - skipped).unwrap_or(buf.len()).min(buf.len());
+        match reader.read(&mut buf[..chunk_length]) {
+            Ok(0) => break,
+            Ok(bytes_read) => skipped += bytes_read as u64,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+
+    return Ok(skipped);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_big_endian_numbers_from_cursor() {
+        let mut reader = Cursor::new([0x01, 0xFF, 0xFE, 0x00, 0x01, 0x00, 0x80, 0x7F]);
+
+        assert_eq!(reader.read_u8().unwrap(), 1);
+        assert_eq!(reader.read_i16::<byteorder::BigEndian>().unwrap(), -2);
+        assert_eq!(reader.read_u32::<byteorder::BigEndian>().unwrap(), 0x0001_0080);
+        assert_eq!(reader.position(), 7);
+        assert_eq!(reader.read_u16::<byteorder::BigEndian>().unwrap_err().kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn skip_stops_at_end_of_input() {
+        let bytes = [0; 600];
+        let mut reader = &bytes[..];
+
+        assert_eq!(skip(&mut reader, 300).unwrap(), 300);
+        assert_eq!(reader.len(), 300);
+        assert_eq!(skip(&mut reader, 400).unwrap(), 300);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn writes_big_endian_numbers() {
+        let mut bytes = Vec::new();
+        bytes.write_u8(1).unwrap();
+        bytes.write_u16::<byteorder::BigEndian>(0x0203).unwrap();
+        bytes.write_u32::<byteorder::BigEndian>(0x0405_0607).unwrap();
+
+        assert_eq!(bytes, [1, 2, 3, 4, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,26 @@
+#![no_std]
+
+#[macro_use]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod descriptor;
+pub mod disassembler;
+pub mod display;
+pub mod index;
+pub mod inspect;
+pub mod io;
+pub mod parser;
+pub mod references;
+pub mod resolve;
+pub mod stackmap;
+pub mod stats;
+pub mod summary;
+pub mod transform;
+pub mod util;
+pub mod validator;
+pub mod writer;
+
+#[cfg(test)]
+mod fixture;
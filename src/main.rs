@@ -1,7 +1,6 @@
-use std::{fs::write, path::{Path, PathBuf}};
+use std::fs::write;
 
-pub mod parser;
-pub mod util;
+use diploma::parser;
 
 fn main() {
     let class_file = match parser::parse_class_file("Test.class") {
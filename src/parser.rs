@@ -1,15 +1,26 @@
-use core::fmt;
-use std::{error, fmt::LowerHex, fs::File, io::BufReader};
-use byteorder::{ReadBytesExt, BigEndian};
+use core::{error, fmt::{self, LowerHex}};
+use alloc::{boxed::Box, string::{String, ToString}, vec::Vec};
+#[cfg(feature = "std")]
+use std::{fs::File, io::BufReader};
+use byteorder::BigEndian;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-use crate::util::modified_utf8_to_string;
+use crate::{io::{self, Cursor, Read, ReadBytesExt}, util::{modified_utf8_to_string, CountingReader}};
 
 // const READER_BUF_SIZE: usize = 8192;
 
 const MAGIC: u32 = 0xCAFEBABE;
 
+/**
+ * Največja kapaciteta, ki se vnaprej alocira za Vec, katerega dolžina je prebrana iz datoteke. Pokvarjena dolžina
+ * (npr. attribute_length blizu 4GB) tako ne povzroči ogromne alokacije, preden branje spodleti zaradi konca datoteke.
+ */
+const MAX_PREALLOCATED_CAPACITY: usize = 4096;
+
+/** Namesto poti do datoteke se uporabi v sporočilih o napakah, kadar se class bere iz toka. */
+const STREAM_FILE_PATH: &str = "<tok>";
+
 trait Tag {
     const TAG: u8;
 }
@@ -18,7 +29,8 @@ trait PossibleTags {
     const TAGS: &'static Vec<u8>;
 }
 
-#[derive(EnumIter, Clone, Copy, Debug)]
+#[derive(EnumIter, Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum MethodHandleReferenceKind {
     GetField = 1,
     GetStatic = 2,
@@ -53,6 +65,12 @@ impl TryFrom<u8> for MethodHandleReferenceKind {
 pub enum ClassAccessFlagMask {
     Public = 0x0001,
     Final = 0x0010,
+    /**
+     * Določa semantiko invokespecial za metode nadrazreda. Classi pred JDK 1.0.2 (45.0 do 45.2) zastavice ne poznajo in je
+     * nimajo nastavljene, od Jave 8 naprej pa JVM šteje, da je vedno nastavljena
+     * (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.1-200-E.1). Pri starih classih (glej
+     * ClassVersion::is_ancient) zato manjkajoča zastavica ni napaka.
+     */
     Super = 0x0020,
     Interface = 0x0200,
     Abstract = 0x0400,
@@ -62,6 +80,18 @@ pub enum ClassAccessFlagMask {
     Module = 0x8000,
 }
 
+pub enum FieldAccessFlagMask {
+    Public = 0x0001,
+    Private = 0x0002,
+    Protected = 0x0004,
+    Static = 0x0008,
+    Final = 0x0010,
+    Volatile = 0x0040,
+    Transient = 0x0080,
+    Synthetic = 0x1000,
+    Enum = 0x4000,
+}
+
 pub enum MethodAccessFlagMask {
     Public = 0x0001,
     Private = 0x0002,
@@ -90,8 +120,30 @@ pub enum InnerClassAccessFlagMask {
     Enum = 0x4000,
 }
 
+/** Nastavitve branja. Privzete nastavitve ob vsaki napaki v class datoteki vrnejo napako. */
+#[derive(Debug, Clone)]
+pub struct ParseOptions {
+    /**
+     * Ob napakah, od katerih si je mogoče opomoči, nadaljuje z branjem in napako zabeleži v strukturi, kjer je nastala.
+     * Trenutno velja le za okvirje StackMapTable (glej AttributeStackMapTable::error).
+     */
+    pub lenient: bool,
+    /**
+     * Največji dovoljen constant_pool_count. Class z večjim se zavrne, preden se constant pool začne brati. Privzeto je
+     * to največja vrednost, ki jo dopušča format (65535), pri branju classov iz nezaupljivih virov pa jo je smiselno zmanjšati.
+     */
+    pub max_constant_pool_entries: u16,
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        return ParseOptions { lenient: false, max_constant_pool_entries: u16::MAX };
+    }
+}
+
 /** https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html */
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ClassFile {
     pub magic: u32,
     pub minor_version: u16,
@@ -111,7 +163,8 @@ pub struct ClassFile {
     pub attributes: Vec<AttributeInfo>
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum CpInfo {
     Utf8(CpUtf8),
     Integer(CpInteger),
@@ -132,7 +185,33 @@ pub enum CpInfo {
     Package(CpPackage)
 }
 
-#[derive(Debug)]
+impl CpInfo {
+    /** Tag, s katerim je vnos zapisan v class datoteki. */
+    pub fn tag(&self) -> u8 {
+        match self {
+            CpInfo::Utf8(_) => CpUtf8::TAG,
+            CpInfo::Integer(_) => CpInteger::TAG,
+            CpInfo::Float(_) => CpFloat::TAG,
+            CpInfo::Long(_) => CpLong::TAG,
+            CpInfo::Double(_) => CpDouble::TAG,
+            CpInfo::Class(_) => CpClass::TAG,
+            CpInfo::String(_) => CpString::TAG,
+            CpInfo::FieldRef(_) => CpFieldRef::TAG,
+            CpInfo::MethodRef(_) => CpMethodRef::TAG,
+            CpInfo::InterfaceMethodRef(_) => CpInterfaceMethodRef::TAG,
+            CpInfo::NameAndType(_) => CpNameAndType::TAG,
+            CpInfo::MethodHandle(_) => CpMethodHandle::TAG,
+            CpInfo::MethodType(_) => CpMethodType::TAG,
+            CpInfo::Dynamic(_) => CpDynamic::TAG,
+            CpInfo::InvokeDynamic(_) => CpInvokeDynamic::TAG,
+            CpInfo::Module(_) => CpModule::TAG,
+            CpInfo::Package(_) => CpPackage::TAG,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpUtf8 {
     // length: u16,
     /** Shranjeno v modified UTF-8 (glej https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.4.7) */
@@ -144,7 +223,8 @@ impl Tag for CpUtf8 {
     const TAG: u8 = 1;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpInteger {
     pub bytes: [u8; 4]
 }
@@ -153,7 +233,8 @@ impl Tag for CpInteger {
     const TAG: u8 = 3;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpFloat {
     pub bytes: [u8; 4]
 }
@@ -162,7 +243,8 @@ impl Tag for CpFloat {
     const TAG: u8 = 4;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpLong {
     pub high_bytes: u32,
     pub low_bytes: u32
@@ -172,7 +254,8 @@ impl Tag for CpLong {
     const TAG: u8 = 5;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpDouble {
     pub high_bytes: u32,
     pub low_bytes: u32
@@ -182,7 +265,8 @@ impl Tag for CpDouble {
     const TAG: u8 = 6;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 /** Class ali interface */
 pub struct CpClass {
     pub name_index: u16
@@ -192,7 +276,8 @@ impl Tag for CpClass {
     const TAG: u8 = 7;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpString {
     pub string_index: u16
 }
@@ -201,7 +286,8 @@ impl Tag for CpString {
     const TAG: u8 = 8;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpFieldRef {
     pub class_index: u16,
     pub name_and_type_index: u16
@@ -211,7 +297,8 @@ impl Tag for CpFieldRef {
     const TAG: u8 = 9;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpMethodRef {
     pub class_index: u16,
     pub name_and_type_index: u16
@@ -221,7 +308,8 @@ impl Tag for CpMethodRef {
     const TAG: u8 = 10;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpInterfaceMethodRef {
     pub class_index: u16,
     pub name_and_type_index: u16
@@ -231,7 +319,8 @@ impl Tag for CpInterfaceMethodRef {
     const TAG: u8 = 11;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpNameAndType {
     pub name_index: u16,
     pub descriptor_index: u16
@@ -241,7 +330,8 @@ impl Tag for CpNameAndType {
     const TAG: u8 = 12;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpMethodHandle {
     pub reference_kind: MethodHandleReferenceKind,
     pub reference_index: u16
@@ -251,7 +341,8 @@ impl Tag for CpMethodHandle {
     const TAG: u8 = 15;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpMethodType {
     pub descriptor_index: u16
 }
@@ -260,7 +351,8 @@ impl Tag for CpMethodType {
     const TAG: u8 = 16;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpDynamic {
     pub bootstrap_method_attr_index: u16,
     pub name_and_type_index: u16
@@ -270,7 +362,8 @@ impl Tag for CpDynamic {
     const TAG: u8 = 17;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpInvokeDynamic {
     pub bootstrap_method_attr_index: u16,
     pub name_and_type_index: u16
@@ -280,7 +373,8 @@ impl Tag for CpInvokeDynamic {
     const TAG: u8 = 18;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpModule {
     pub name_index: u16
 }
@@ -289,7 +383,8 @@ impl Tag for CpModule {
     const TAG: u8 = 19;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpPackage {
     pub name_index: u16
 }
@@ -298,7 +393,8 @@ impl Tag for CpPackage {
     const TAG: u8 = 20;
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct FieldInfo {
     pub access_flags: u16,
     pub name_index: u16,
@@ -307,7 +403,8 @@ pub struct FieldInfo {
     pub attributes: Vec<AttributeInfo>
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MethodInfo {
     pub access_flags: u16,
     pub name_index: u16,
@@ -316,7 +413,8 @@ pub struct MethodInfo {
     pub attributes: Vec<AttributeInfo>
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AttributeInfo {
     ConstantValue(AttributeConstantValue),
     Code(AttributeCode),
@@ -350,6 +448,44 @@ pub enum AttributeInfo {
     PermittedSubclasses(AttributePermittedSubclasses),
 }
 
+impl AttributeInfo {
+    /** Ime atributa, kot je zapisano v constant poolu, npr. `Code`. */
+    pub fn name(&self) -> &'static str {
+        match self {
+            AttributeInfo::ConstantValue(_) => AttributeConstantValue::ATTRIBUTE_NAME,
+            AttributeInfo::Code(_) => AttributeCode::ATTRIBUTE_NAME,
+            AttributeInfo::StackMapTable(_) => AttributeStackMapTable::ATTRIBUTE_NAME,
+            AttributeInfo::Exceptions(_) => AttributeExceptions::ATTRIBUTE_NAME,
+            AttributeInfo::InnerClasses(_) => AttributeInnerClasses::ATTRIBUTE_NAME,
+            AttributeInfo::EnclosingMethod(_) => AttributeEnclosingMethod::ATTRIBUTE_NAME,
+            AttributeInfo::Synthetic(_) => AttributeSynthetic::ATTRIBUTE_NAME,
+            AttributeInfo::Signature(_) => AttributeSignature::ATTRIBUTE_NAME,
+            AttributeInfo::SourceFile(_) => AttributeSourceFile::ATTRIBUTE_NAME,
+            AttributeInfo::SourceDebugExtension(_) => AttributeSourceDebugExtension::ATTRIBUTE_NAME,
+            AttributeInfo::LineNumberTable(_) => AttributeLineNumberTable::ATTRIBUTE_NAME,
+            AttributeInfo::LocalVariableTable(_) => AttributeLocalVariableTable::ATTRIBUTE_NAME,
+            AttributeInfo::LocalVariableTypeTable(_) => AttributeLocalVariableTypeTable::ATTRIBUTE_NAME,
+            AttributeInfo::Deprecated(_) => AttributeDeprecated::ATTRIBUTE_NAME,
+            AttributeInfo::RuntimeVisibleAnnotations(_) => AttributeRuntimeVisibleAnnotations::ATTRIBUTE_NAME,
+            AttributeInfo::RuntimeInvisibleAnnotations(_) => AttributeRuntimeInvisibleAnnotations::ATTRIBUTE_NAME,
+            AttributeInfo::RuntimeVisibleParameterAnnotations(_) => AttributeRuntimeVisibleParameterAnnotations::ATTRIBUTE_NAME,
+            AttributeInfo::RuntimeInvisibleParameterAnnotations(_) => AttributeRuntimeInvisibleParameterAnnotations::ATTRIBUTE_NAME,
+            AttributeInfo::RuntimeVisibleTypeAnnotations(_) => AttributeRuntimeVisibleTypeAnnotations::ATTRIBUTE_NAME,
+            AttributeInfo::RuntimeInvisibleTypeAnnotations(_) => AttributeRuntimeInvisibleTypeAnnotations::ATTRIBUTE_NAME,
+            AttributeInfo::AnnotationDefault(_) => AttributeAnnotationDefault::ATTRIBUTE_NAME,
+            AttributeInfo::BootstrapMethods(_) => AttributeBootstrapMethods::ATTRIBUTE_NAME,
+            AttributeInfo::MethodParameters(_) => AttributeMethodParameters::ATTRIBUTE_NAME,
+            AttributeInfo::Module(_) => AttributeModule::ATTRIBUTE_NAME,
+            AttributeInfo::ModulePackages(_) => AttributeModulePackages::ATTRIBUTE_NAME,
+            AttributeInfo::ModuleMainClass(_) => AttributeModuleMainClass::ATTRIBUTE_NAME,
+            AttributeInfo::NestHost(_) => AttributeNestHost::ATTRIBUTE_NAME,
+            AttributeInfo::NestMembers(_) => AttributeNestMembers::ATTRIBUTE_NAME,
+            AttributeInfo::Record(_) => AttributeRecord::ATTRIBUTE_NAME,
+            AttributeInfo::PermittedSubclasses(_) => AttributePermittedSubclasses::ATTRIBUTE_NAME,
+        }
+    }
+}
+
 trait AttributeName {
     const ATTRIBUTE_NAME: &'static str;
 }
@@ -358,7 +494,8 @@ trait AttributeLength {
     const ATTRIBUTE_LENGTH: u32;
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeConstantValue {
     pub constantvalue_index: u16
 }
@@ -367,7 +504,12 @@ impl AttributeName for AttributeConstantValue {
     const ATTRIBUTE_NAME: &'static str = "ConstantValue";
 }
 
-#[derive(Debug)]
+impl AttributeLength for AttributeConstantValue {
+    const ATTRIBUTE_LENGTH: u32 = 2;
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ExceptionTableEntry {
     pub start_pc: u16,
     pub end_pc: u16,
@@ -375,7 +517,8 @@ pub struct ExceptionTableEntry {
     pub catch_type: u16
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeCode {
     pub attribute_length: u32,
     pub max_stack: u16,
@@ -392,7 +535,8 @@ impl AttributeName for AttributeCode {
     const ATTRIBUTE_NAME: &'static str = "Code";
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum VerificationTypeInfo {
     TopVariable(VerificationTypeTopVariableInfo),
     IntegerVariable(VerificationTypeIntegerVariableInfo),
@@ -405,42 +549,90 @@ pub enum VerificationTypeInfo {
     DoubleVariable(VerificationTypeDoubleVariableInfo),
 }
 
-#[derive(Debug)]
+impl VerificationTypeInfo {
+    /** Tag, s katerim je vrsta zapisana v class datoteki. */
+    pub fn tag(&self) -> u8 {
+        match self {
+            VerificationTypeInfo::TopVariable(_) => VerificationTypeTopVariableInfo::TAG,
+            VerificationTypeInfo::IntegerVariable(_) => VerificationTypeIntegerVariableInfo::TAG,
+            VerificationTypeInfo::FloatVariable(_) => VerificationTypeFloatVariableInfo::TAG,
+            VerificationTypeInfo::NullVariable(_) => VerificationTypeNullVariableInfo::TAG,
+            VerificationTypeInfo::UninitializedThisVariable(_) => VerificationTypeUninitializedThisVariableInfo::TAG,
+            VerificationTypeInfo::ObjectVariable(_) => VerificationTypeObjectVariableInfo::TAG,
+            VerificationTypeInfo::UninitializedVariable(_) => VerificationTypeUninitializedVariableInfo::TAG,
+            VerificationTypeInfo::LongVariable(_) => VerificationTypeLongVariableInfo::TAG,
+            VerificationTypeInfo::DoubleVariable(_) => VerificationTypeDoubleVariableInfo::TAG,
+        }
+    }
+
+    /**
+     * Vrsta, ki jo določa le tag. Vrne None za neznane tage ter za ObjectVariable in UninitializedVariable,
+     * ki potrebujeta še cpool_index oz. offset (glej object in uninitialized).
+     */
+    pub fn from_tag(tag: u8) -> Option<VerificationTypeInfo> {
+        match tag {
+            VerificationTypeTopVariableInfo::TAG => Some(VerificationTypeInfo::TopVariable(VerificationTypeTopVariableInfo {})),
+            VerificationTypeIntegerVariableInfo::TAG => Some(VerificationTypeInfo::IntegerVariable(VerificationTypeIntegerVariableInfo {})),
+            VerificationTypeFloatVariableInfo::TAG => Some(VerificationTypeInfo::FloatVariable(VerificationTypeFloatVariableInfo {})),
+            VerificationTypeNullVariableInfo::TAG => Some(VerificationTypeInfo::NullVariable(VerificationTypeNullVariableInfo {})),
+            VerificationTypeUninitializedThisVariableInfo::TAG => Some(VerificationTypeInfo::UninitializedThisVariable(VerificationTypeUninitializedThisVariableInfo {})),
+            VerificationTypeLongVariableInfo::TAG => Some(VerificationTypeInfo::LongVariable(VerificationTypeLongVariableInfo {})),
+            VerificationTypeDoubleVariableInfo::TAG => Some(VerificationTypeInfo::DoubleVariable(VerificationTypeDoubleVariableInfo {})),
+            _ => None,
+        }
+    }
+
+    pub fn object(cpool_index: u16) -> VerificationTypeInfo {
+        return VerificationTypeInfo::ObjectVariable(VerificationTypeObjectVariableInfo { cpool_index });
+    }
+
+    pub fn uninitialized(offset: u16) -> VerificationTypeInfo {
+        return VerificationTypeInfo::UninitializedVariable(VerificationTypeUninitializedVariableInfo { offset });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerificationTypeTopVariableInfo {}
 
 impl Tag for VerificationTypeTopVariableInfo {
     const TAG: u8 = 0;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerificationTypeIntegerVariableInfo {}
 
 impl Tag for VerificationTypeIntegerVariableInfo {
     const TAG: u8 = 1;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerificationTypeFloatVariableInfo {}
 
 impl Tag for VerificationTypeFloatVariableInfo {
     const TAG: u8 = 2;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerificationTypeNullVariableInfo {}
 
 impl Tag for VerificationTypeNullVariableInfo {
     const TAG: u8 = 5;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerificationTypeUninitializedThisVariableInfo {}
 
 impl Tag for VerificationTypeUninitializedThisVariableInfo {
     const TAG: u8 = 6;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerificationTypeObjectVariableInfo {
     pub cpool_index: u16,
 }
@@ -449,7 +641,8 @@ impl Tag for VerificationTypeObjectVariableInfo {
     const TAG: u8 = 7;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerificationTypeUninitializedVariableInfo {
     pub offset: u16,
 }
@@ -458,21 +651,24 @@ impl Tag for VerificationTypeUninitializedVariableInfo {
     const TAG: u8 = 8;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerificationTypeLongVariableInfo {}
 
 impl Tag for VerificationTypeLongVariableInfo {
     const TAG: u8 = 4;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct VerificationTypeDoubleVariableInfo {}
 
 impl Tag for VerificationTypeDoubleVariableInfo {
     const TAG: u8 = 3;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum StackMapFrame {
     SameFrame(StackMapSameFrame),
     SameLocals1StackItemFrame(StackMapSameLocals1StackItemFrame),
@@ -488,7 +684,8 @@ trait TagRange {
     const TAG_UPPER: u8;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StackMapSameFrame {
     pub frame_type: u8,
 }
@@ -498,7 +695,8 @@ impl TagRange for StackMapSameFrame {
     const TAG_UPPER: u8 = 63;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StackMapSameLocals1StackItemFrame {
     pub frame_type: u8,
     pub stack_entry: VerificationTypeInfo,
@@ -509,7 +707,8 @@ impl TagRange for StackMapSameLocals1StackItemFrame {
     const TAG_UPPER: u8 = 127;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StackMapSameLocals1StackItemFrameExtended {
     pub frame_type: u8,
     pub offset_delta: u16,
@@ -520,7 +719,8 @@ impl Tag for StackMapSameLocals1StackItemFrameExtended {
     const TAG: u8 = 247;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StackMapChopFrame {
     pub frame_type: u8,
     pub offset_delta: u16,
@@ -531,7 +731,8 @@ impl TagRange for StackMapChopFrame {
     const TAG_UPPER: u8 = 250;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StackMapSameFrameExtended  {
     pub frame_type: u8,
     pub offset_delta: u16,
@@ -541,7 +742,8 @@ impl Tag for StackMapSameFrameExtended {
     const TAG: u8 = 251;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StackMapAppendFrame  {
     pub frame_type: u8,
     pub offset_delta: u16,
@@ -553,7 +755,8 @@ impl TagRange for StackMapAppendFrame {
     const TAG_UPPER: u8 = 254;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct StackMapFullFrame {
     pub frame_type: u8,
     pub offset_delta: u16,
@@ -567,18 +770,25 @@ impl Tag for StackMapFullFrame {
     const TAG: u8 = 255;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeStackMapTable {
     pub attribute_length: u32,
     // number_of_entries: u16,
-    pub entries: Vec<StackMapFrame>
+    pub entries: Vec<StackMapFrame>,
+    /**
+     * Le pri branju z ParseOptions::lenient: opis napake v okvirju, zaradi katere je bil ostanek atributa preskočen.
+     * entries tedaj vsebuje le okvirje pred napačnim, vsi okvirji od njega naprej so izpuščeni.
+     */
+    pub error: Option<String>,
 }
 
 impl AttributeName for AttributeStackMapTable {
     const ATTRIBUTE_NAME: &'static str = "StackMapTable";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeExceptions {
     pub attribute_length: u32,
     // number_of_exceptions: u16,
@@ -589,7 +799,8 @@ impl AttributeName for AttributeExceptions {
     const ATTRIBUTE_NAME: &'static str = "Exceptions";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeInnerClassesClass {
     pub inner_class_info_index: u16,
     pub outer_class_info_index: u16,
@@ -597,7 +808,8 @@ pub struct AttributeInnerClassesClass {
     pub inner_class_access_flags: u16,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeInnerClasses {
     pub attribute_length: u32,
     // number_of_classes: u16,
@@ -608,7 +820,8 @@ impl AttributeName for AttributeInnerClasses {
     const ATTRIBUTE_NAME: &'static str = "InnerClasses";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeEnclosingMethod {
     pub class_index: u16,
     pub method_index: u16,
@@ -622,7 +835,8 @@ impl AttributeLength for AttributeEnclosingMethod {
     const ATTRIBUTE_LENGTH: u32 = 4;
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeSynthetic {}
 
 impl AttributeName for AttributeSynthetic {
@@ -633,7 +847,8 @@ impl AttributeLength for AttributeSynthetic {
     const ATTRIBUTE_LENGTH: u32 = 0;
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeSignature {
     pub signature_index: u16,
 }
@@ -646,7 +861,8 @@ impl AttributeLength for AttributeSignature {
     const ATTRIBUTE_LENGTH: u32 = 2;
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeSourceFile {
     pub sourcefile_index: u16,
 }
@@ -659,7 +875,8 @@ impl AttributeLength for AttributeSourceFile {
     const ATTRIBUTE_LENGTH: u32 = 2;
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeSourceDebugExtension {
     // attribute_length: u32,
     pub debug_extension: Vec<u8>,
@@ -669,13 +886,15 @@ impl AttributeName for AttributeSourceDebugExtension {
     const ATTRIBUTE_NAME: &'static str = "SourceDebugExtension";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeLineNumberTableEntry {
     pub start_pc: u16,
     pub line_number: u16,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeLineNumberTable {
     pub attribute_length: u32,
     // line_number_table_length: u16,
@@ -686,7 +905,8 @@ impl AttributeName for AttributeLineNumberTable {
     const ATTRIBUTE_NAME: &'static str = "LineNumberTable";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeLocalVariableTableEntry {
     pub start_pc: u16,
     pub length: u16,
@@ -695,7 +915,8 @@ pub struct AttributeLocalVariableTableEntry {
     pub index: u16,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeLocalVariableTable {
     pub attribute_length: u32,
     // local_variable_table_length: u16,
@@ -706,7 +927,8 @@ impl AttributeName for AttributeLocalVariableTable {
     const ATTRIBUTE_NAME: &'static str = "LocalVariableTable";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeLocalVariableTypeTableEntry {
     pub start_pc: u16,
     pub length: u16,
@@ -715,7 +937,8 @@ pub struct AttributeLocalVariableTypeTableEntry {
     pub index: u16,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeLocalVariableTypeTable {
     pub attribute_length: u32,
     // local_variable_table_length: u16,
@@ -723,10 +946,11 @@ pub struct AttributeLocalVariableTypeTable {
 }
 
 impl AttributeName for AttributeLocalVariableTypeTable {
-    const ATTRIBUTE_NAME: &'static str = "LocalVariableTypeTableEntry";
+    const ATTRIBUTE_NAME: &'static str = "LocalVariableTypeTable";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeDeprecated {}
 
 impl AttributeName for AttributeDeprecated {
@@ -737,7 +961,8 @@ impl AttributeLength for AttributeDeprecated {
     const ATTRIBUTE_LENGTH: u32 = 0;
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AttributeAnnotationsElementValue {
     Byte(AttributeAnnotationsElementValueByte),
     Char(AttributeAnnotationsElementValueChar),
@@ -754,7 +979,29 @@ pub enum AttributeAnnotationsElementValue {
     ArrayType(AttributeAnnotationsElementValueArrayType),
 }
 
-#[derive(Debug)]
+impl AttributeAnnotationsElementValue {
+    /** Tag, s katerim je vrednost zapisana v class datoteki, npr. `s` za String. */
+    pub fn tag(&self) -> u8 {
+        match self {
+            AttributeAnnotationsElementValue::Byte(_) => AttributeAnnotationsElementValueByte::TAG,
+            AttributeAnnotationsElementValue::Char(_) => AttributeAnnotationsElementValueChar::TAG,
+            AttributeAnnotationsElementValue::Double(_) => AttributeAnnotationsElementValueDouble::TAG,
+            AttributeAnnotationsElementValue::Float(_) => AttributeAnnotationsElementValueFloat::TAG,
+            AttributeAnnotationsElementValue::Int(_) => AttributeAnnotationsElementValueInt::TAG,
+            AttributeAnnotationsElementValue::Long(_) => AttributeAnnotationsElementValueLong::TAG,
+            AttributeAnnotationsElementValue::Short(_) => AttributeAnnotationsElementValueShort::TAG,
+            AttributeAnnotationsElementValue::Boolean(_) => AttributeAnnotationsElementValueBoolean::TAG,
+            AttributeAnnotationsElementValue::String(_) => AttributeAnnotationsElementValueString::TAG,
+            AttributeAnnotationsElementValue::EnumClass(_) => AttributeAnnotationsElementValueEnumClass::TAG,
+            AttributeAnnotationsElementValue::Class(_) => AttributeAnnotationsElementValueClass::TAG,
+            AttributeAnnotationsElementValue::AnnotationInterface(_) => AttributeAnnotationsElementValueAnnotationInterface::TAG,
+            AttributeAnnotationsElementValue::ArrayType(_) => AttributeAnnotationsElementValueArrayType::TAG,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueByte {
     pub const_value_index: u16,
 }
@@ -763,7 +1010,8 @@ impl Tag for AttributeAnnotationsElementValueByte {
     const TAG: u8 = b'B';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueChar {
     pub const_value_index: u16,
 }
@@ -772,7 +1020,8 @@ impl Tag for AttributeAnnotationsElementValueChar {
     const TAG: u8 = b'C';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueDouble {
     pub const_value_index: u16,
 }
@@ -781,7 +1030,8 @@ impl Tag for AttributeAnnotationsElementValueDouble {
     const TAG: u8 = b'D';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueFloat {
     pub const_value_index: u16,
 }
@@ -790,7 +1040,8 @@ impl Tag for AttributeAnnotationsElementValueFloat {
     const TAG: u8 = b'F';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueInt {
     pub const_value_index: u16,
 }
@@ -799,7 +1050,8 @@ impl Tag for AttributeAnnotationsElementValueInt {
     const TAG: u8 = b'I';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueLong {
     pub const_value_index: u16,
 }
@@ -808,7 +1060,8 @@ impl Tag for AttributeAnnotationsElementValueLong {
     const TAG: u8 = b'J';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueShort {
     pub const_value_index: u16,
 }
@@ -817,7 +1070,8 @@ impl Tag for AttributeAnnotationsElementValueShort {
     const TAG: u8 = b'S';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueBoolean {
     pub const_value_index: u16,
 }
@@ -826,7 +1080,8 @@ impl Tag for AttributeAnnotationsElementValueBoolean {
     const TAG: u8 = b'Z';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueString {
     pub const_value_index: u16,
 }
@@ -835,7 +1090,8 @@ impl Tag for AttributeAnnotationsElementValueString {
     const TAG: u8 = b's';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueEnumClass {
     pub type_name_index: u16,
     pub const_name_index: u16,
@@ -845,7 +1101,8 @@ impl Tag for AttributeAnnotationsElementValueEnumClass {
     const TAG: u8 = b'e';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueClass {
     pub class_info_index: u16,
 }
@@ -854,7 +1111,8 @@ impl Tag for AttributeAnnotationsElementValueClass {
     const TAG: u8 = b'c';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueAnnotationInterface {
     pub annotation_value: AttributeRuntimeAnnotationsEntry,
 }
@@ -863,7 +1121,8 @@ impl Tag for AttributeAnnotationsElementValueAnnotationInterface {
     const TAG: u8 = b'@';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValueArrayType {
     // num_values: u16,
     pub values: Vec<AttributeAnnotationsElementValue>,
@@ -873,20 +1132,23 @@ impl Tag for AttributeAnnotationsElementValueArrayType {
     const TAG: u8 = b'[';
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationsElementValuePair {
     pub element_name_index: u16,
     pub element_value: AttributeAnnotationsElementValue
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeAnnotationsEntry {
     pub type_index: u16,
     // num_element_value_pairs: u16,
     pub element_value_pairs: Vec<AttributeAnnotationsElementValuePair>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeVisibleAnnotations {
     pub attribute_length: u32,
     // num_annotations: u16,
@@ -897,7 +1159,8 @@ impl AttributeName for AttributeRuntimeVisibleAnnotations {
     const ATTRIBUTE_NAME: &'static str = "RuntimeVisibleAnnotations";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeInvisibleAnnotations {
     pub attribute_length: u32,
     // num_annotations: u16,
@@ -908,10 +1171,16 @@ impl AttributeName for AttributeRuntimeInvisibleAnnotations {
     const ATTRIBUTE_NAME: &'static str = "RuntimeInvisibleAnnotations";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeVisibleParameterAnnotations {
     pub attribute_length: u32,
     // num_parameters: u8,
+    /**
+     * Element i vsebuje anotacije i-tega parametra (prazen, če parameter nima anotacij), zato je dolžina enaka num_parameters.
+     * num_parameters je lahko manjši od števila parametrov v deskriptorju, npr. brez sintetičnih parametrov konstruktorjev
+     * notranjih razredov (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.7.18).
+     */
     pub parameter_annotations: Vec<Vec<AttributeRuntimeAnnotationsEntry>>,
 }
 
@@ -919,10 +1188,16 @@ impl AttributeName for AttributeRuntimeVisibleParameterAnnotations {
     const ATTRIBUTE_NAME: &'static str = "RuntimeVisibleParameterAnnotations";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeInvisibleParameterAnnotations {
     pub attribute_length: u32,
-    // num_parameters: u16,
+    // num_parameters: u8,
+    /**
+     * Element i vsebuje anotacije i-tega parametra (prazen, če parameter nima anotacij), zato je dolžina enaka num_parameters.
+     * num_parameters je lahko manjši od števila parametrov v deskriptorju, npr. brez sintetičnih parametrov konstruktorjev
+     * notranjih razredov (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.7.18).
+     */
     pub parameter_annotations: Vec<Vec<AttributeRuntimeAnnotationsEntry>>,
 }
 
@@ -930,7 +1205,8 @@ impl AttributeName for AttributeRuntimeInvisibleParameterAnnotations {
     const ATTRIBUTE_NAME: &'static str = "RuntimeInvisibleParameterAnnotations";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntryTypeParameterTarget {
     pub type_parameter_index: u8,
 }
@@ -939,7 +1215,8 @@ pub struct AttributeRuntimeTypeAnnotationsEntryTypeParameterTarget {
 //     const TAGS: &'static Vec<u8> = [0x00, 0x01];
 // }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntrySuperTypeTarget {
     pub supertype_index: u16,
 }
@@ -948,7 +1225,8 @@ pub struct AttributeRuntimeTypeAnnotationsEntrySuperTypeTarget {
 //     const TAGS: Vec<u8> = [0x10].into();
 // }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntryTypeParameterBoundTarget {
     pub type_parameter_index: u8,
     pub bound_index: u8,
@@ -958,7 +1236,8 @@ pub struct AttributeRuntimeTypeAnnotationsEntryTypeParameterBoundTarget {
 //     const TAGS: Vec<u8> = [0x11, 0x12].into();
 // }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntryEmptyTarget {}
 
 // impl PossibleTags for AttributeRuntimeTypeAnnotationsEntryEmptyTarget {
@@ -966,7 +1245,8 @@ pub struct AttributeRuntimeTypeAnnotationsEntryEmptyTarget {}
 // }
 
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntryFormalParameterTarget {
     pub formal_parameter_index: u8,
 }
@@ -975,7 +1255,8 @@ pub struct AttributeRuntimeTypeAnnotationsEntryFormalParameterTarget {
 //     const TAGS: Vec<u8> = [0x16].into();
 // }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntryThrowsTarget {
     pub throws_type_index: u16,
 }
@@ -984,14 +1265,16 @@ pub struct AttributeRuntimeTypeAnnotationsEntryThrowsTarget {
 //     const TAGS: Vec<u8> = [0x17].into();
 // }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntryLocalvarTargetTableEntry {
     pub start_pc: u16,
     pub length: u16,
     pub index: u16,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntryLocalvarTarget {
     // table_length: u16,
     pub table: Vec<AttributeRuntimeTypeAnnotationsEntryLocalvarTargetTableEntry>,
@@ -1001,7 +1284,8 @@ pub struct AttributeRuntimeTypeAnnotationsEntryLocalvarTarget {
 //     const TAGS: Vec<u8> = [0x40, 0x41].into();
 // }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntryCatchTarget {
     pub exception_table_index: u16,
 }
@@ -1010,7 +1294,8 @@ pub struct AttributeRuntimeTypeAnnotationsEntryCatchTarget {
 //     const TAGS: Vec<u8> = [0x42].into();
 // }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntryOffsetTarget {
     pub offset: u16,
 }
@@ -1019,7 +1304,8 @@ pub struct AttributeRuntimeTypeAnnotationsEntryOffsetTarget {
 //     const TAGS: Vec<u8> = [0x43, 0x44, 0x45, 0x46].into();
 // }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntryTypeArgumentTarget {
     pub offset: u16,
     pub type_argument_index: u8,
@@ -1029,7 +1315,8 @@ pub struct AttributeRuntimeTypeAnnotationsEntryTypeArgumentTarget {
 //     const TAGS: Vec<u8> = [0x47, 0x48, 0x49, 0x4A, 0x4B].into();
 // }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AttributeRuntimeTypeAnnotationsEntryTargetInfo {
     TypeParameterTarget(AttributeRuntimeTypeAnnotationsEntryTypeParameterTarget),
     SuperTypeTarget(AttributeRuntimeTypeAnnotationsEntrySuperTypeTarget),
@@ -1043,13 +1330,15 @@ pub enum AttributeRuntimeTypeAnnotationsEntryTargetInfo {
     TypeArgumentTarget(AttributeRuntimeTypeAnnotationsEntryTypeArgumentTarget),
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeVisibleTypeAnnotationsEntryTargetPathEntry {
     pub type_path_kind: u8,
     pub type_argument_index: u8,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeTypeAnnotationsEntry {
     pub target_type: u8,
     pub target_info: AttributeRuntimeTypeAnnotationsEntryTargetInfo,
@@ -1060,7 +1349,8 @@ pub struct AttributeRuntimeTypeAnnotationsEntry {
     pub element_value_pairs: Vec<AttributeAnnotationsElementValuePair>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeVisibleTypeAnnotations {
     pub attribute_length: u32,
     // num_annotations: u16,
@@ -1071,7 +1361,8 @@ impl AttributeName for AttributeRuntimeVisibleTypeAnnotations {
     const ATTRIBUTE_NAME: &'static str = "RuntimeVisibleTypeAnnotations";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRuntimeInvisibleTypeAnnotations {
     pub attribute_length: u32,
     // num_annotations: u16,
@@ -1082,7 +1373,8 @@ impl AttributeName for AttributeRuntimeInvisibleTypeAnnotations {
     const ATTRIBUTE_NAME: &'static str = "RuntimeInvisibleTypeAnnotations";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeAnnotationDefault {
     pub attribute_length: u32,
     pub default_value: AttributeAnnotationsElementValue,
@@ -1092,14 +1384,16 @@ impl AttributeName for AttributeAnnotationDefault {
     const ATTRIBUTE_NAME: &'static str = "AnnotationDefault";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeBootstrapMethodsEntry {
     pub bootstrap_method_ref: u16,
     // num_bootstrap_arguments: u16,
     pub bootstrap_arguments: Vec<u16>
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeBootstrapMethods {
     pub attribute_length: u32,
     // num_bootstrap_methods: u16,
@@ -1110,13 +1404,15 @@ impl AttributeName for AttributeBootstrapMethods {
     const ATTRIBUTE_NAME: &'static str = "BootstrapMethods";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeMethodParametersEntry {
     pub name_index: u16,
     pub access_flags: u16,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeMethodParameters {
     pub attribute_length: u32,
     // parameters_count: u8,
@@ -1127,14 +1423,16 @@ impl AttributeName for AttributeMethodParameters {
     const ATTRIBUTE_NAME: &'static str = "MethodParameters";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeModuleRequiresEntry {
     pub requires_index: u16,
     pub requires_flags: u16,
     pub requires_version_index: u16,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeModuleExportsEntry {
     pub exports_index: u16,
     pub exports_flags: u16,
@@ -1142,7 +1440,8 @@ pub struct AttributeModuleExportsEntry {
     pub exports_to_index: Vec<u16>
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeModuleOpensEntry {
     pub opens_index: u16,
     pub opens_flags: u16,
@@ -1150,14 +1449,16 @@ pub struct AttributeModuleOpensEntry {
     pub opens_to_index: Vec<u16>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeModuleProvidesEntry {
     pub provides_index: u16,
     // provides_with_count: u16,
     pub provides_with_index: Vec<u16>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeModule {
     pub attribute_length: u32,
 
@@ -1181,7 +1482,8 @@ impl AttributeName for AttributeModule {
     const ATTRIBUTE_NAME: &'static str = "Module";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeModulePackages {
     pub attribute_length: u32,
     // package_count: u16,
@@ -1192,7 +1494,8 @@ impl AttributeName for AttributeModulePackages {
     const ATTRIBUTE_NAME: &'static str = "ModulePackages";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeModuleMainClass {
     pub main_class_index: u16,
 }
@@ -1205,7 +1508,8 @@ impl AttributeLength for AttributeModuleMainClass {
     const ATTRIBUTE_LENGTH: u32 = 2;
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeNestHost {
     pub host_class_index: u16,
 }
@@ -1218,7 +1522,8 @@ impl AttributeLength for AttributeNestHost {
     const ATTRIBUTE_LENGTH: u32 = 2;
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeNestMembers {
     pub attribute_length: u32,
     // number_of_classes: u16,
@@ -1229,7 +1534,8 @@ impl AttributeName for AttributeNestMembers {
     const ATTRIBUTE_NAME: &'static str = "NestMembers";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRecordComponentInfo {
     pub name_index: u16,
     pub descriptor_index: u16,
@@ -1237,7 +1543,8 @@ pub struct AttributeRecordComponentInfo {
     pub attributes: Vec<AttributeInfo>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributeRecord {
     pub attribute_length: u32,
     // components_count: u16,
@@ -1248,7 +1555,8 @@ impl AttributeName for AttributeRecord {
     const ATTRIBUTE_NAME: &'static str = "Record";
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AttributePermittedSubclasses {
     pub attribute_length: u32,
     // number_of_classes: u16,
@@ -1259,7 +1567,7 @@ impl AttributeName for AttributePermittedSubclasses {
     const ATTRIBUTE_NAME: &'static str = "PermittedSubclasses";
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct MalformedClassFile {
     file_path: String,
     msg: String
@@ -1293,11 +1601,69 @@ impl fmt::Display for MalformedClassFile {
 
 impl error::Error for MalformedClassFile {}
 
+/** Branje iz datotečnega sistema je na voljo le s feature `std` (privzeto vklopljen). */
+#[cfg(feature = "std")]
 pub fn parse_class_file(file_path: &str) -> Result<ClassFile, Box<dyn error::Error>> {
+    return parse_class_file_with_options(file_path, &ParseOptions::default());
+}
+
+#[cfg(feature = "std")]
+pub fn parse_class_file_with_options(file_path: &str, options: &ParseOptions) -> Result<ClassFile, Box<dyn error::Error>> {
     let file = File::open(file_path)?;
     // let mut reader: BufReader<File> = BufReader::with_capacity(READER_BUF_SIZE, file);
     let mut reader: BufReader<File> = BufReader::new(file);
 
+    return read_class_file(file_path, &mut reader, options);
+}
+
+/**
+ * Prebere en class iz toka in bralnik pusti postavljen točno za njegovim zadnjim bajtom, tako da lahko klicatelj
+ * nadaljuje z branjem (npr. naslednjega classa v vsebniku brez predpone z dolžino). Ne preverja, da je tok prebran do konca.
+ * Bere po nekaj bajtov naenkrat, zato naj bo bralnik v pomnilniku (&[u8], Cursor) ali std::io::BufReader. Deluje tudi brez
+ * feature `std`, takrat je Read iz crate::io.
+ */
+pub fn parse_one(reader: &mut impl Read) -> Result<ClassFile, Box<dyn error::Error>> {
+    return parse_one_with_options(reader, &ParseOptions::default());
+}
+
+pub fn parse_one_with_options(reader: &mut impl Read, options: &ParseOptions) -> Result<ClassFile, Box<dyn error::Error>> {
+    return read_class_file(STREAM_FILE_PATH, reader, options);
+}
+
+/**
+ * Razčleni class iz bajtov in ga vrne serializiranega v JSON. Napake so vrnjene kot nizi, kar je primerno za izvoz
+ * v WASM (wasm-bindgen). Indeksi v constant pool niso razrešeni, struktura je enaka kot pri ClassFile.
+ */
+#[cfg(feature = "serde")]
+pub fn parse_bytes_to_json(bytes: &[u8]) -> Result<String, String> {
+    let class_file = parse_one(&mut Cursor::new(bytes)).map_err(|err| err.to_string())?;
+
+    return serde_json::to_string(&class_file).map_err(|err| err.to_string());
+}
+
+/**
+ * Prebere le glavo in constant pool, ostanek classa (polja, metode, atributi) se ne bere. Vrnjen constant pool
+ * uporablja enako indeksiranje kot ClassFile::constant_pool: je 1-indexed, na indeksu 0 in za vsakim Long ter Double
+ * vnosom je "dummy" vnos.
+ */
+pub fn parse_constant_pool_only(reader: &mut impl Read) -> Result<Vec<CpInfo>, Box<dyn error::Error>> {
+    let magic = reader.read_u32::<BigEndian>()?;
+    if magic != MAGIC {
+        return Err(MalformedClassFile::entry_wrong_value(STREAM_FILE_PATH, "MAGIC", magic, MAGIC).into());
+    }
+
+    let _minor_version = reader.read_u16::<BigEndian>()?;
+    let _major_version = reader.read_u16::<BigEndian>()?;
+
+    let constant_pool_count = reader.read_u16::<BigEndian>()?;
+    return read_constant_pool(STREAM_FILE_PATH, reader, constant_pool_count, &ParseOptions::default());
+}
+
+fn bounded_capacity(count: u32) -> usize {
+    return usize::try_from(count).unwrap_or(usize::MAX).min(MAX_PREALLOCATED_CAPACITY);
+}
+
+fn read_class_file(file_path: &str, reader: &mut impl Read, options: &ParseOptions) -> Result<ClassFile, Box<dyn error::Error>> {
     let magic = reader.read_u32::<BigEndian>()?;
     if magic != MAGIC {
         return Err(MalformedClassFile::entry_wrong_value(file_path, "MAGIC", magic, MAGIC).into());
@@ -1307,7 +1673,7 @@ pub fn parse_class_file(file_path: &str) -> Result<ClassFile, Box<dyn error::Err
     let major_version = reader.read_u16::<BigEndian>()?;
 
     let constant_pool_count = reader.read_u16::<BigEndian>()?;
-    let constant_pool = read_constant_pool(file_path, &mut reader, constant_pool_count)?;
+    let constant_pool = read_constant_pool(file_path, reader, constant_pool_count, options)?;
 
     let access_flags = reader.read_u16::<BigEndian>()?;
 
@@ -1315,17 +1681,17 @@ pub fn parse_class_file(file_path: &str) -> Result<ClassFile, Box<dyn error::Err
     let super_class = reader.read_u16::<BigEndian>()?;
 
     let interfaces_count = reader.read_u16::<BigEndian>()?;
-    let interfaces = read_interfaces(&mut reader, interfaces_count)?;
+    let interfaces = read_interfaces(reader, interfaces_count)?;
     
 
     let fields_count = reader.read_u16::<BigEndian>()?;
-    let fields = read_fields(file_path, &mut reader, fields_count, &constant_pool)?;
+    let fields = read_fields(file_path, reader, fields_count, &constant_pool, options)?;
 
     let methods_count = reader.read_u16::<BigEndian>()?;
-    let methods = read_methods(file_path, &mut reader, methods_count, &constant_pool)?;
+    let methods = read_methods(file_path, reader, methods_count, &constant_pool, options)?;
 
     let attributes_count = reader.read_u16::<BigEndian>()?;
-    let attributes = read_attributes(file_path, &mut reader, attributes_count, &constant_pool)?;
+    let attributes = read_attributes(file_path, reader, attributes_count, &constant_pool, options)?;
 
     return Ok(ClassFile {
         magic,
@@ -1343,25 +1709,56 @@ pub fn parse_class_file(file_path: &str) -> Result<ClassFile, Box<dyn error::Err
     });
 }
 
-fn read_constant_pool(class_file_path: &str, reader: &mut BufReader<File>, constant_pool_count: u16) -> Result<Vec<CpInfo>, Box<dyn error::Error>> {
+fn read_constant_pool(class_file_path: &str, reader: &mut impl Read, constant_pool_count: u16, options: &ParseOptions) -> Result<Vec<CpInfo>, Box<dyn error::Error>> {
+    check_constant_pool_count(class_file_path, constant_pool_count)?;
+    if constant_pool_count > options.max_constant_pool_entries {
+        return Err(MalformedClassFile { file_path: class_file_path.into(), msg: format!("constant_pool_count {} presega omejitev {}", constant_pool_count, options.max_constant_pool_entries) }.into());
+    }
+
     // constant pool je 1-indexed in vsebuje constant_pool_count - 1 vnosov. Vec je zato za 1 večji in na indeksu 0 vsebuje "dummy" vnos.
-    let mut constant_pool = Vec::with_capacity((constant_pool_count).into());
+    let mut constant_pool = Vec::with_capacity(bounded_capacity(constant_pool_count.into()));
     constant_pool.push(CpInfo::Integer(CpInteger { bytes: [0; 4] }));
 
-    for _ in 0..constant_pool_count - 1 {
-        constant_pool.push(read_constant_pool_entry(class_file_path, reader)?);
+    while constant_pool.len() < constant_pool_count.into() {
+        let entry = read_constant_pool_entry(class_file_path, reader)?;
+        let takes_two_entries = matches!(entry, CpInfo::Long(_) | CpInfo::Double(_));
+        constant_pool.push(entry);
+
+        // Long in Double zasedeta dva vnosa, drugi je neuporaben in prav tako vsebuje "dummy" vnos
+        if takes_two_entries {
+            constant_pool.push(CpInfo::Integer(CpInteger { bytes: [0; 4] }));
+        }
     }
+    check_constant_pool_length(class_file_path, constant_pool_count, constant_pool.len())?;
 
     return Ok(constant_pool);
 }
 
-fn read_constant_pool_entry(class_file_path: &str, reader: &mut BufReader<File>) -> Result<CpInfo, Box<dyn error::Error>> {
+/** constant_pool_count je število vnosov + 1, zato je 0 neveljavna vrednost. */
+fn check_constant_pool_count(class_file_path: &str, constant_pool_count: u16) -> Result<(), Box<dyn error::Error>> {
+    if constant_pool_count == 0 {
+        return Err(MalformedClassFile { file_path: class_file_path.into(), msg: "constant_pool_count je 0, mora biti vsaj 1".into() }.into());
+    }
+
+    return Ok(());
+}
+
+/** Long ali Double na zadnjem mestu bi s svojim drugim vnosom segel čez constant_pool_count. */
+fn check_constant_pool_length(class_file_path: &str, constant_pool_count: u16, constant_pool_length: usize) -> Result<(), Box<dyn error::Error>> {
+    if constant_pool_length > constant_pool_count.into() {
+        return Err(MalformedClassFile { file_path: class_file_path.into(), msg: format!("Long ali Double na indeksu {} presega constant_pool_count {}", constant_pool_count - 1, constant_pool_count) }.into());
+    }
+
+    return Ok(());
+}
+
+fn read_constant_pool_entry(class_file_path: &str, reader: &mut impl Read) -> Result<CpInfo, Box<dyn error::Error>> {
     let tag = reader.read_u8()?;
 
     match tag {
         CpUtf8::TAG => {
             let length = reader.read_u16::<BigEndian>()?;
-            let mut bytes: Vec<u8> = Vec::with_capacity(length.into());
+            let mut bytes: Vec<u8> = Vec::with_capacity(bounded_capacity(length.into()));
             for _ in 0..length {
                 bytes.push(reader.read_u8()?);
             }
@@ -1459,8 +1856,8 @@ fn read_constant_pool_entry(class_file_path: &str, reader: &mut BufReader<File>)
     }
 }
 
-fn read_interfaces(reader: &mut BufReader<File>, interfaces_count: u16) -> Result<Vec<u16>, Box<dyn error::Error>> {
-    let mut interfaces = Vec::with_capacity(interfaces_count.into());
+fn read_interfaces(reader: &mut impl Read, interfaces_count: u16) -> Result<Vec<u16>, Box<dyn error::Error>> {
+    let mut interfaces = Vec::with_capacity(bounded_capacity(interfaces_count.into()));
     for _ in 0..interfaces_count {
         interfaces.push(reader.read_u16::<BigEndian>()?);
     }
@@ -1468,47 +1865,96 @@ fn read_interfaces(reader: &mut BufReader<File>, interfaces_count: u16) -> Resul
     return Ok(interfaces);
 }
 
-fn read_fields(class_file_path: &str, reader: &mut BufReader<File>, fields_count: u16, constant_pool: &Vec<CpInfo>) -> Result<Vec<FieldInfo>, Box<dyn error::Error>> {
-    let mut fields = Vec::with_capacity(fields_count.into());
+fn read_fields(class_file_path: &str, reader: &mut impl Read, fields_count: u16, constant_pool: &Vec<CpInfo>, options: &ParseOptions) -> Result<Vec<FieldInfo>, Box<dyn error::Error>> {
+    let mut fields = Vec::with_capacity(bounded_capacity(fields_count.into()));
     for _ in 0..fields_count {
-        fields.push(read_field(class_file_path, reader, constant_pool)?);
+        fields.push(read_field(class_file_path, reader, constant_pool, options)?);
     }
 
     return Ok(fields);
 }
 
-fn read_field(class_file_path: &str, reader: &mut BufReader<File>, constant_pool: &Vec<CpInfo>) -> Result<FieldInfo, Box<dyn error::Error>> {
+fn read_field(class_file_path: &str, reader: &mut impl Read, constant_pool: &Vec<CpInfo>, options: &ParseOptions) -> Result<FieldInfo, Box<dyn error::Error>> {
     let access_flags = reader.read_u16::<BigEndian>()?;
     let name_index = reader.read_u16::<BigEndian>()?;
     let descriptor_index = reader.read_u16::<BigEndian>()?;
     let attributes_count = reader.read_u16::<BigEndian>()?;
-    let attributes = read_attributes(class_file_path, reader, attributes_count, constant_pool)?;
+    let attributes = read_attributes(class_file_path, reader, attributes_count, constant_pool, options)?;
     
     return Ok(FieldInfo { access_flags, name_index, descriptor_index, attributes });
 }
 
-fn read_attributes(class_file_path: &str, reader: &mut BufReader<File>, attributes_count: u16, constant_pool: &Vec<CpInfo>) -> Result<Vec<AttributeInfo>, Box<dyn error::Error>> {
-    let mut attributes = Vec::with_capacity(attributes_count.into());
+fn read_attributes(class_file_path: &str, reader: &mut impl Read, attributes_count: u16, constant_pool: &Vec<CpInfo>, options: &ParseOptions) -> Result<Vec<AttributeInfo>, Box<dyn error::Error>> {
+    let mut attributes = Vec::with_capacity(bounded_capacity(attributes_count.into()));
     for _ in 0..attributes_count {
-        attributes.push(read_attribute(class_file_path, reader, constant_pool)?)
+        attributes.push(read_attribute(class_file_path, reader, constant_pool, options)?)
     }
 
     return Ok(attributes);
 }
 
-fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_pool: &Vec<CpInfo>) -> Result<AttributeInfo, Box<dyn error::Error>> {
+fn read_attribute(class_file_path: &str, reader: &mut impl Read, constant_pool: &Vec<CpInfo>, options: &ParseOptions) -> Result<AttributeInfo, Box<dyn error::Error>> {
     let attribute_name_index = reader.read_u16::<BigEndian>()?;
     let attribute_length = reader.read_u32::<BigEndian>()?;
 
-    let attribute_name_cp_utf8 = match &constant_pool[usize::from(attribute_name_index)] {
-        CpInfo::Utf8(cp_utf8) => cp_utf8,
-        _ => {
+    // dyn Read, ker read_attribute_info rekurzivno bere gnezdene atribute (npr. v Code) in bi se tip bralnika sicer gnezdil
+    let mut counting_reader = CountingReader::new(reader as &mut dyn Read);
+    let attribute = read_attribute_info(class_file_path, &mut counting_reader, attribute_name_index, attribute_length, constant_pool, options)?;
+    check_attribute_consumed(class_file_path, &attribute, attribute_length, counting_reader.position())?;
+
+    return Ok(attribute);
+}
+
+/** Vsebina atributa se bere po strukturi, zato mora biti na koncu prebranih točno attribute_length bajtov. */
+fn check_attribute_consumed(class_file_path: &str, attribute: &AttributeInfo, attribute_length: u32, bytes_read: u64) -> Result<(), Box<dyn error::Error>> {
+    if bytes_read != u64::from(attribute_length) {
+        return Err(MalformedClassFile { file_path: class_file_path.into(), msg: format!("atribut {} ima attribute_length {}, njegova vsebina pa obsega {} bajtov", attribute.name(), attribute_length, bytes_read) }.into());
+    }
+
+    return Ok(());
+}
+
+/**
+ * Preskoči count atributov brez razčlenjevanja, vsakega po njegovem attribute_length. Reader ostane na začetku strukture,
+ * ki sledi atributom. Imena atributov se ne preverjajo, zato constant pool ni potreben.
+ */
+pub fn skip_attributes(reader: &mut impl Read, count: u16) -> Result<(), Box<dyn error::Error>> {
+    for _ in 0..count {
+        let _attribute_name_index = reader.read_u16::<BigEndian>()?;
+        let attribute_length = reader.read_u32::<BigEndian>()?;
+        let skipped = io::skip(reader, attribute_length.into())?;
+        if skipped != u64::from(attribute_length) {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+        }
+    }
+
+    return Ok(());
+}
+
+/** Atributi s fiksno dolžino se berejo brez upoštevanja attribute_length, zato bi napačna dolžina razsinhronizirala bralnik. */
+fn check_attribute_length<T: AttributeName + AttributeLength>(class_file_path: &str, attribute_length: u32) -> Result<(), Box<dyn error::Error>> {
+    if attribute_length != T::ATTRIBUTE_LENGTH {
+        return Err(MalformedClassFile { file_path: class_file_path.into(), msg: format!("atribut {} ima attribute_length {}, pričakovan je {}", T::ATTRIBUTE_NAME, attribute_length, T::ATTRIBUTE_LENGTH) }.into());
+    }
+
+    return Ok(());
+}
+
+/** Prebere vsebino atributa, ki sledi attribute_name_index in attribute_length. */
+fn read_attribute_info(class_file_path: &str, reader: &mut impl Read, attribute_name_index: u16, attribute_length: u32, constant_pool: &Vec<CpInfo>, options: &ParseOptions) -> Result<AttributeInfo, Box<dyn error::Error>> {
+    let attribute_name_cp_utf8 = match constant_pool.get(usize::from(attribute_name_index)) {
+        Some(CpInfo::Utf8(cp_utf8)) => cp_utf8,
+        None => {
+            return Err(MalformedClassFile { file_path: class_file_path.into(), msg: format!("attribute_name_index {} je izven constant pool (velikost {})", attribute_name_index, constant_pool.len()) }.into());
+        },
+        Some(_) => {
             return Err(MalformedClassFile { file_path: class_file_path.into(), msg: format!("attribute_name_index {} ne vodi to CpUtf8", attribute_name_index) }.into());
         }
     };
 
     match attribute_name_cp_utf8.converted.as_str() {
         AttributeConstantValue::ATTRIBUTE_NAME => {
+            check_attribute_length::<AttributeConstantValue>(class_file_path, attribute_length)?;
             let constantvalue_index = reader.read_u16::<BigEndian>()?;
             return Ok(AttributeInfo::ConstantValue(AttributeConstantValue { constantvalue_index }));
         },
@@ -1523,7 +1969,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
             }
 
             let exception_table_length = reader.read_u16::<BigEndian>()?;
-            let mut exception_table = Vec::with_capacity(exception_table_length.into());
+            let mut exception_table = Vec::with_capacity(bounded_capacity(exception_table_length.into()));
             for _ in 0..exception_table_length {
                 exception_table.push(ExceptionTableEntry {
                     start_pc: reader.read_u16::<BigEndian>()?,
@@ -1534,22 +1980,38 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
             }
 
             let attributes_count = reader.read_u16::<BigEndian>()?;
-            let attributes = read_attributes(class_file_path, reader, attributes_count, constant_pool)?;
+            let attributes = read_attributes(class_file_path, reader, attributes_count, constant_pool, options)?;
 
             return Ok(AttributeInfo::Code(AttributeCode { attribute_length, max_stack, max_locals, code, exception_table, attributes}))
         },
         AttributeStackMapTable::ATTRIBUTE_NAME => {
-            let number_of_entries = reader.read_u16::<BigEndian>()?;
-            let mut entries = Vec::with_capacity(number_of_entries.into());
+            let mut counting_reader = CountingReader::new(reader.by_ref());
+            let number_of_entries = counting_reader.read_u16::<BigEndian>()?;
+            let mut entries = Vec::with_capacity(bounded_capacity(number_of_entries.into()));
             for _ in 0..number_of_entries {
-                entries.push(read_stack_map_frame(class_file_path, reader)?);
+                match read_stack_map_frame(class_file_path, &mut counting_reader) {
+                    Ok(frame) => entries.push(frame),
+                    Err(err) if options.lenient => {
+                        // atribut je omejen z attribute_length, zato se lahko preskoči do konca in nadaljuje z naslednjim
+                        let Some(remaining_length) = u64::from(attribute_length).checked_sub(counting_reader.position()) else {
+                            return Err(err);
+                        };
+                        let skipped = io::skip(&mut counting_reader, remaining_length)?;
+                        if skipped != remaining_length {
+                            return Err(err);
+                        }
+
+                        return Ok(AttributeInfo::StackMapTable(AttributeStackMapTable { attribute_length, entries, error: Some(err.to_string()) }));
+                    },
+                    Err(err) => return Err(err),
+                };
             }
 
-            return Ok(AttributeInfo::StackMapTable(AttributeStackMapTable { attribute_length, entries }));
+            return Ok(AttributeInfo::StackMapTable(AttributeStackMapTable { attribute_length, entries, error: None }));
         },
         AttributeExceptions::ATTRIBUTE_NAME => {
             let number_of_exceptions = reader.read_u16::<BigEndian>()?;
-            let mut exception_index_table = Vec::with_capacity(number_of_exceptions.into());
+            let mut exception_index_table = Vec::with_capacity(bounded_capacity(number_of_exceptions.into()));
             for _ in 0..number_of_exceptions {
                 exception_index_table.push(reader.read_u16::<BigEndian>()?);
             }
@@ -1558,7 +2020,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeInnerClasses::ATTRIBUTE_NAME => {
             let number_of_classes = reader.read_u16::<BigEndian>()?;
-            let mut classes = Vec::with_capacity(number_of_classes.into());
+            let mut classes = Vec::with_capacity(bounded_capacity(number_of_classes.into()));
             for _ in 0..number_of_classes {
                 classes.push(AttributeInnerClassesClass {
                     inner_class_info_index: reader.read_u16::<BigEndian>()?,
@@ -1571,26 +2033,30 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
             return Ok(AttributeInfo::InnerClasses(AttributeInnerClasses { attribute_length, classes }));
         },
         AttributeEnclosingMethod::ATTRIBUTE_NAME => {
+            check_attribute_length::<AttributeEnclosingMethod>(class_file_path, attribute_length)?;
             let class_index = reader.read_u16::<BigEndian>()?;
             let method_index = reader.read_u16::<BigEndian>()?;
 
             return Ok(AttributeInfo::EnclosingMethod(AttributeEnclosingMethod { class_index, method_index }));
         },
         AttributeSynthetic::ATTRIBUTE_NAME => {
+            check_attribute_length::<AttributeSynthetic>(class_file_path, attribute_length)?;
             return Ok(AttributeInfo::Synthetic(AttributeSynthetic {}));
         },
         AttributeSignature::ATTRIBUTE_NAME => {
+            check_attribute_length::<AttributeSignature>(class_file_path, attribute_length)?;
             let signature_index = reader.read_u16::<BigEndian>()?;
 
             return Ok(AttributeInfo::Signature(AttributeSignature { signature_index }));
         },
         AttributeSourceFile::ATTRIBUTE_NAME => {
+            check_attribute_length::<AttributeSourceFile>(class_file_path, attribute_length)?;
             let sourcefile_index = reader.read_u16::<BigEndian>()?;
 
             return Ok(AttributeInfo::SourceFile(AttributeSourceFile { sourcefile_index }));
         },
         AttributeSourceDebugExtension::ATTRIBUTE_NAME => {
-            let mut debug_extension = Vec::with_capacity(attribute_length.try_into()?);
+            let mut debug_extension = Vec::with_capacity(bounded_capacity(attribute_length));
             for _ in 0..attribute_length {
                 debug_extension.push(reader.read_u8()?);
             }
@@ -1599,7 +2065,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeLineNumberTable::ATTRIBUTE_NAME => {
             let line_number_table_length = reader.read_u16::<BigEndian>()?;
-            let mut line_number_table = Vec::with_capacity(line_number_table_length.into());
+            let mut line_number_table = Vec::with_capacity(bounded_capacity(line_number_table_length.into()));
             for _ in 0..line_number_table_length {
                 line_number_table.push(AttributeLineNumberTableEntry {
                     start_pc: reader.read_u16::<BigEndian>()?,
@@ -1611,7 +2077,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeLocalVariableTable::ATTRIBUTE_NAME => {
             let local_variable_table_length = reader.read_u16::<BigEndian>()?;
-            let mut local_variable_table = Vec::with_capacity(local_variable_table_length.into());
+            let mut local_variable_table = Vec::with_capacity(bounded_capacity(local_variable_table_length.into()));
             for _ in 0..local_variable_table_length {
                 local_variable_table.push(AttributeLocalVariableTableEntry {
                     start_pc: reader.read_u16::<BigEndian>()?,
@@ -1626,7 +2092,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeLocalVariableTypeTable::ATTRIBUTE_NAME => {
             let local_variable_table_length = reader.read_u16::<BigEndian>()?;
-            let mut local_variable_table = Vec::with_capacity(local_variable_table_length.into());
+            let mut local_variable_table = Vec::with_capacity(bounded_capacity(local_variable_table_length.into()));
             for _ in 0..local_variable_table_length {
                 local_variable_table.push(AttributeLocalVariableTypeTableEntry {
                     start_pc: reader.read_u16::<BigEndian>()?,
@@ -1640,11 +2106,12 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
             return Ok(AttributeInfo::LocalVariableTypeTable(AttributeLocalVariableTypeTable { attribute_length, local_variable_table }));
         },
         AttributeDeprecated::ATTRIBUTE_NAME => {
+            check_attribute_length::<AttributeDeprecated>(class_file_path, attribute_length)?;
             return Ok(AttributeInfo::Deprecated(AttributeDeprecated {}));
         },
         AttributeRuntimeVisibleAnnotations::ATTRIBUTE_NAME => {
             let num_annotations = reader.read_u16::<BigEndian>()?;
-            let mut annotations = Vec::with_capacity(num_annotations.into());
+            let mut annotations = Vec::with_capacity(bounded_capacity(num_annotations.into()));
             for _ in 0..num_annotations {
                 annotations.push(read_runtime_annotations_entry(class_file_path, reader)?);
             }
@@ -1653,7 +2120,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeRuntimeInvisibleAnnotations::ATTRIBUTE_NAME => {
             let num_annotations = reader.read_u16::<BigEndian>()?;
-            let mut annotations = Vec::with_capacity(num_annotations.into());
+            let mut annotations = Vec::with_capacity(bounded_capacity(num_annotations.into()));
             for _ in 0..num_annotations {
                 annotations.push(read_runtime_annotations_entry(class_file_path, reader)?);
             }
@@ -1662,10 +2129,10 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeRuntimeVisibleParameterAnnotations::ATTRIBUTE_NAME => {
             let num_parameters = reader.read_u8()?;
-            let mut parameter_annotations = Vec::with_capacity(num_parameters.into());
+            let mut parameter_annotations = Vec::with_capacity(bounded_capacity(num_parameters.into()));
             for _ in 0..num_parameters {
                 let num_annotations = reader.read_u16::<BigEndian>()?;
-                let mut annotations = Vec::with_capacity(num_annotations.into());
+                let mut annotations = Vec::with_capacity(bounded_capacity(num_annotations.into()));
                 for _ in 0..num_annotations {
                     annotations.push(read_runtime_annotations_entry(class_file_path, reader)?)
                 }
@@ -1677,10 +2144,10 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeRuntimeInvisibleParameterAnnotations::ATTRIBUTE_NAME => {
             let num_parameters = reader.read_u8()?;
-            let mut parameter_annotations = Vec::with_capacity(num_parameters.into());
+            let mut parameter_annotations = Vec::with_capacity(bounded_capacity(num_parameters.into()));
             for _ in 0..num_parameters {
                 let num_annotations = reader.read_u16::<BigEndian>()?;
-                let mut annotations = Vec::with_capacity(num_annotations.into());
+                let mut annotations = Vec::with_capacity(bounded_capacity(num_annotations.into()));
                 for _ in 0..num_annotations {
                     annotations.push(read_runtime_annotations_entry(class_file_path, reader)?)
                 }
@@ -1692,7 +2159,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeRuntimeVisibleTypeAnnotations::ATTRIBUTE_NAME => {
             let num_annotations = reader.read_u16::<BigEndian>()?;
-            let mut annotations = Vec::with_capacity(num_annotations.into());
+            let mut annotations = Vec::with_capacity(bounded_capacity(num_annotations.into()));
             for _ in 0..num_annotations {
                 annotations.push(read_runtime_type_annotations_entry(class_file_path, reader)?);
             }
@@ -1701,7 +2168,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeRuntimeInvisibleTypeAnnotations::ATTRIBUTE_NAME => {
             let num_annotations = reader.read_u16::<BigEndian>()?;
-            let mut annotations = Vec::with_capacity(num_annotations.into());
+            let mut annotations = Vec::with_capacity(bounded_capacity(num_annotations.into()));
             for _ in 0..num_annotations {
                 annotations.push(read_runtime_type_annotations_entry(class_file_path, reader)?);
             }
@@ -1714,11 +2181,11 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeBootstrapMethods::ATTRIBUTE_NAME => {
             let num_bootstrap_methods = reader.read_u16::<BigEndian>()?;
-            let mut bootstrap_methods = Vec::with_capacity(num_bootstrap_methods.into());
+            let mut bootstrap_methods = Vec::with_capacity(bounded_capacity(num_bootstrap_methods.into()));
             for _ in 0..num_bootstrap_methods {
                 let bootstrap_method_ref = reader.read_u16::<BigEndian>()?;
                 let num_bootstrap_arguments = reader.read_u16::<BigEndian>()?;
-                let mut bootstrap_arguments = Vec::with_capacity(num_bootstrap_arguments.into());
+                let mut bootstrap_arguments = Vec::with_capacity(bounded_capacity(num_bootstrap_arguments.into()));
                 for _ in 0..num_bootstrap_arguments {
                     bootstrap_arguments.push(reader.read_u16::<BigEndian>()?);
                 }
@@ -1733,7 +2200,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeMethodParameters::ATTRIBUTE_NAME => {
             let parameters_count = reader.read_u8()?;
-            let mut parameters = Vec::with_capacity(parameters_count.into());
+            let mut parameters = Vec::with_capacity(bounded_capacity(parameters_count.into()));
             for _ in 0..parameters_count {
                 parameters.push(AttributeMethodParametersEntry {
                     name_index: reader.read_u16::<BigEndian>()?,
@@ -1749,7 +2216,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
             let module_version_index = reader.read_u16::<BigEndian>()?;
 
             let requires_count = reader.read_u16::<BigEndian>()?;
-            let mut requires = Vec::with_capacity(requires_count.into());
+            let mut requires = Vec::with_capacity(bounded_capacity(requires_count.into()));
             for _ in 0..requires_count {
                 requires.push(AttributeModuleRequiresEntry {
                     requires_index: reader.read_u16::<BigEndian>()?,
@@ -1759,13 +2226,13 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
             }
 
             let exports_count = reader.read_u16::<BigEndian>()?;
-            let mut exports = Vec::with_capacity(exports_count.into());
+            let mut exports = Vec::with_capacity(bounded_capacity(exports_count.into()));
             for _ in 0..exports_count {
                 let exports_index = reader.read_u16::<BigEndian>()?;
                 let exports_flags = reader.read_u16::<BigEndian>()?;
 
                 let exports_to_count = reader.read_u16::<BigEndian>()?;
-                let mut exports_to_index = Vec::with_capacity(exports_to_count.into());
+                let mut exports_to_index = Vec::with_capacity(bounded_capacity(exports_to_count.into()));
                 for _ in 0..exports_to_count {
                     exports_to_index.push(reader.read_u16::<BigEndian>()?);
                 }
@@ -1778,13 +2245,13 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
             }
 
             let opens_count = reader.read_u16::<BigEndian>()?;
-            let mut opens = Vec::with_capacity(opens_count.into());
+            let mut opens = Vec::with_capacity(bounded_capacity(opens_count.into()));
             for _ in 0..opens_count {
                 let opens_index = reader.read_u16::<BigEndian>()?;
                 let opens_flags = reader.read_u16::<BigEndian>()?;
 
                 let opens_to_count = reader.read_u16::<BigEndian>()?;
-                let mut opens_to_index = Vec::with_capacity(opens_to_count.into());
+                let mut opens_to_index = Vec::with_capacity(bounded_capacity(opens_to_count.into()));
                 for _ in 0..opens_to_count {
                     opens_to_index.push(reader.read_u16::<BigEndian>()?);
                 }
@@ -1797,18 +2264,18 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
             }
 
             let uses_count = reader.read_u16::<BigEndian>()?;
-            let mut uses_index = Vec::with_capacity(uses_count.into());
+            let mut uses_index = Vec::with_capacity(bounded_capacity(uses_count.into()));
             for _ in 0..uses_count {
                 uses_index.push(reader.read_u16::<BigEndian>()?);
             }
 
             let provides_count = reader.read_u16::<BigEndian>()?;
-            let mut provides = Vec::with_capacity(provides_count.into());
+            let mut provides = Vec::with_capacity(bounded_capacity(provides_count.into()));
             for _ in 0..provides_count {
                 let provides_index = reader.read_u16::<BigEndian>()?;
 
                 let provides_with_count = reader.read_u16::<BigEndian>()?;
-                let mut provides_with_index = Vec::with_capacity(provides_with_count.into());
+                let mut provides_with_index = Vec::with_capacity(bounded_capacity(provides_with_count.into()));
                 for _ in 0..provides_with_count {
                     provides_with_index.push(reader.read_u16::<BigEndian>()?);
                 }
@@ -1833,7 +2300,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeModulePackages::ATTRIBUTE_NAME => {
             let package_count = reader.read_u16::<BigEndian>()?;
-            let mut package_index = Vec::with_capacity(package_count.into());
+            let mut package_index = Vec::with_capacity(bounded_capacity(package_count.into()));
             for _ in 0..package_count {
                 package_index.push(reader.read_u16::<BigEndian>()?);
             }
@@ -1841,16 +2308,18 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
             return Ok(AttributeInfo::ModulePackages(AttributeModulePackages { attribute_length, package_index }));
         },
         AttributeModuleMainClass::ATTRIBUTE_NAME => {
+            check_attribute_length::<AttributeModuleMainClass>(class_file_path, attribute_length)?;
             let main_class_index = reader.read_u16::<BigEndian>()?;
             return Ok(AttributeInfo::ModuleMainClass(AttributeModuleMainClass { main_class_index }));
         },
         AttributeNestHost::ATTRIBUTE_NAME => {
+            check_attribute_length::<AttributeNestHost>(class_file_path, attribute_length)?;
             let host_class_index = reader.read_u16::<BigEndian>()?;
             return Ok(AttributeInfo::NestHost(AttributeNestHost { host_class_index }));
         },
         AttributeNestMembers::ATTRIBUTE_NAME => {
             let number_of_classes = reader.read_u16::<BigEndian>()?;
-            let mut classes = Vec::with_capacity(number_of_classes.into());
+            let mut classes = Vec::with_capacity(bounded_capacity(number_of_classes.into()));
             for _ in 0..number_of_classes {
                 classes.push(reader.read_u16::<BigEndian>()?);
             }
@@ -1859,13 +2328,13 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributeRecord::ATTRIBUTE_NAME => {
             let components_count = reader.read_u16::<BigEndian>()?;
-            let mut components = Vec::with_capacity(components_count.into());
+            let mut components = Vec::with_capacity(bounded_capacity(components_count.into()));
             for _ in 0..components_count {
                 let name_index = reader.read_u16::<BigEndian>()?;
                 let descriptor_index = reader.read_u16::<BigEndian>()?;
 
                 let attributes_count = reader.read_u16::<BigEndian>()?;
-                let attributes = read_attributes(class_file_path, reader, attributes_count, constant_pool)?;
+                let attributes = read_attributes(class_file_path, reader, attributes_count, constant_pool, options)?;
 
                 components.push(AttributeRecordComponentInfo {
                     name_index,
@@ -1878,7 +2347,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
         },
         AttributePermittedSubclasses::ATTRIBUTE_NAME => {
             let number_of_classes = reader.read_u16::<BigEndian>()?;
-            let mut classes = Vec::with_capacity(number_of_classes.into());
+            let mut classes = Vec::with_capacity(bounded_capacity(number_of_classes.into()));
             for _ in 0..number_of_classes {
                 classes.push(reader.read_u16::<BigEndian>()?);
             }
@@ -1891,7 +2360,7 @@ fn read_attribute(class_file_path: &str, reader: &mut BufReader<File>, constant_
     }
 }
 
-fn read_stack_map_frame(class_file_path: &str, reader: &mut BufReader<File>, ) -> Result<StackMapFrame, Box<dyn error::Error>> {
+fn read_stack_map_frame(class_file_path: &str, reader: &mut impl Read, ) -> Result<StackMapFrame, Box<dyn error::Error>> {
     let frame_type = reader.read_u8()?;
     match frame_type {
         StackMapSameFrame::TAG_LOWER..=StackMapSameFrame::TAG_UPPER => {
@@ -1917,10 +2386,19 @@ fn read_stack_map_frame(class_file_path: &str, reader: &mut BufReader<File>, ) -
         StackMapAppendFrame::TAG_LOWER..=StackMapAppendFrame::TAG_UPPER => {
             let offset_delta = reader.read_u16::<BigEndian>()?;
 
-            let number_of_locals: usize = (frame_type - 251).into();
+            // match tega ne dopusti, a napačna meja v TagRange bi sicer tiho prebrala napačno število lokalnih spremenljivk
+            let number_of_locals: usize = frame_type.wrapping_sub(251).into();
+            if !(1..=3).contains(&number_of_locals) {
+                return Err(MalformedClassFile { file_path: class_file_path.into(), msg: format!("AppendFrame s frame_type {} bi imel {} lokalnih spremenljivk, dovoljene so 1 do 3", frame_type, number_of_locals) }.into());
+            }
+
             let mut locals = Vec::with_capacity(number_of_locals);
-            for _ in 0..number_of_locals {
-                locals.push(read_verification_type_info(class_file_path, reader)?);
+            for i in 0..number_of_locals {
+                let local = read_verification_type_info(class_file_path, reader).map_err(|err| MalformedClassFile {
+                    file_path: class_file_path.into(),
+                    msg: format!("AppendFrame s frame_type {} je okrnjen, lokalne spremenljivke {} od {} ni mogoče prebrati: {}", frame_type, i + 1, number_of_locals, err),
+                })?;
+                locals.push(local);
             }
 
             return Ok(StackMapFrame::AppendFrame(StackMapAppendFrame { frame_type, offset_delta, locals }));
@@ -1929,13 +2407,13 @@ fn read_stack_map_frame(class_file_path: &str, reader: &mut BufReader<File>, ) -
             let offset_delta = reader.read_u16::<BigEndian>()?;
 
             let number_of_locals = reader.read_u16::<BigEndian>()?;
-            let mut locals = Vec::with_capacity(number_of_locals.into());
+            let mut locals = Vec::with_capacity(bounded_capacity(number_of_locals.into()));
             for _ in 0..number_of_locals {
                 locals.push(read_verification_type_info(class_file_path, reader)?);
             }
 
             let number_of_stack_items = reader.read_u16::<BigEndian>()?;
-            let mut stack = Vec::with_capacity(number_of_stack_items.into());
+            let mut stack = Vec::with_capacity(bounded_capacity(number_of_stack_items.into()));
             for _ in 0..number_of_stack_items {
                 stack.push(read_verification_type_info(class_file_path, reader)?);
             }
@@ -1948,48 +2426,28 @@ fn read_stack_map_frame(class_file_path: &str, reader: &mut BufReader<File>, ) -
     }
 }
 
-fn read_verification_type_info(class_file_path: &str, reader: &mut BufReader<File>) -> Result<VerificationTypeInfo, Box<dyn error::Error>> {
+fn read_verification_type_info(class_file_path: &str, reader: &mut impl Read) -> Result<VerificationTypeInfo, Box<dyn error::Error>> {
     let tag = reader.read_u8()?;
     match tag {
-        VerificationTypeTopVariableInfo::TAG => {
-            return Ok(VerificationTypeInfo::TopVariable(VerificationTypeTopVariableInfo {}));
-        },
-        VerificationTypeIntegerVariableInfo::TAG => {
-            return Ok(VerificationTypeInfo::IntegerVariable(VerificationTypeIntegerVariableInfo {}));
-        },
-        VerificationTypeFloatVariableInfo::TAG => {
-            return Ok(VerificationTypeInfo::FloatVariable(VerificationTypeFloatVariableInfo {}));
-        },
-        VerificationTypeNullVariableInfo::TAG => {
-            return Ok(VerificationTypeInfo::NullVariable(VerificationTypeNullVariableInfo {}));
-        },
-        VerificationTypeUninitializedThisVariableInfo::TAG => {
-            return Ok(VerificationTypeInfo::UninitializedThisVariable(VerificationTypeUninitializedThisVariableInfo {}));
-        },
         VerificationTypeObjectVariableInfo::TAG => {
             let cpool_index = reader.read_u16::<BigEndian>()?;
-            return Ok(VerificationTypeInfo::ObjectVariable(VerificationTypeObjectVariableInfo { cpool_index }));
+            return Ok(VerificationTypeInfo::object(cpool_index));
         },
         VerificationTypeUninitializedVariableInfo::TAG => {
             let offset = reader.read_u16::<BigEndian>()?;
-            return Ok(VerificationTypeInfo::UninitializedVariable(VerificationTypeUninitializedVariableInfo { offset }));
-        },
-        VerificationTypeLongVariableInfo::TAG => {
-            return Ok(VerificationTypeInfo::LongVariable(VerificationTypeLongVariableInfo {}));
+            return Ok(VerificationTypeInfo::uninitialized(offset));
         },
-        VerificationTypeDoubleVariableInfo::TAG => {
-            return Ok(VerificationTypeInfo::DoubleVariable(VerificationTypeDoubleVariableInfo {}));
-        }
         _ => {
-            return Err(MalformedClassFile { file_path: class_file_path.into(), msg: format!("Neznan VerificationTypeInfo tag: {:#0x}", tag) }.into());
+            return VerificationTypeInfo::from_tag(tag)
+                .ok_or(MalformedClassFile { file_path: class_file_path.into(), msg: format!("Neznan VerificationTypeInfo tag: {:#0x}", tag) }.into());
         }
     }
 }
 
-fn read_runtime_annotations_entry(class_file_path: &str, reader: &mut BufReader<File>) -> Result<AttributeRuntimeAnnotationsEntry, Box<dyn error::Error>> {
+fn read_runtime_annotations_entry(class_file_path: &str, reader: &mut impl Read) -> Result<AttributeRuntimeAnnotationsEntry, Box<dyn error::Error>> {
     let type_index = reader.read_u16::<BigEndian>()?;
     let num_element_value_pairs = reader.read_u16::<BigEndian>()?;
-    let mut element_value_pairs = Vec::with_capacity(num_element_value_pairs.into());
+    let mut element_value_pairs = Vec::with_capacity(bounded_capacity(num_element_value_pairs.into()));
     for _ in 0..num_element_value_pairs {
         element_value_pairs.push(read_annotations_element_value_pair(class_file_path, reader)?);
     }
@@ -1997,14 +2455,14 @@ fn read_runtime_annotations_entry(class_file_path: &str, reader: &mut BufReader<
     return Ok(AttributeRuntimeAnnotationsEntry { type_index, element_value_pairs })
 }
 
-fn read_annotations_element_value_pair(class_file_path: &str, reader: &mut BufReader<File>) -> Result<AttributeAnnotationsElementValuePair, Box<dyn error::Error>> {
+fn read_annotations_element_value_pair(class_file_path: &str, reader: &mut impl Read) -> Result<AttributeAnnotationsElementValuePair, Box<dyn error::Error>> {
     let element_name_index = reader.read_u16::<BigEndian>()?;
     let element_value = read_annotations_element_value(class_file_path, reader)?;
 
     return Ok(AttributeAnnotationsElementValuePair { element_name_index, element_value })
 }
 
-fn read_annotations_element_value(class_file_path: &str, reader: &mut BufReader<File>) -> Result<AttributeAnnotationsElementValue, Box<dyn error::Error>> {
+fn read_annotations_element_value(class_file_path: &str, reader: &mut impl Read) -> Result<AttributeAnnotationsElementValue, Box<dyn error::Error>> {
     let tag = reader.read_u8()?;
     match tag {
         AttributeAnnotationsElementValueByte::TAG => {
@@ -2059,7 +2517,7 @@ fn read_annotations_element_value(class_file_path: &str, reader: &mut BufReader<
         },
         AttributeAnnotationsElementValueArrayType::TAG => {
             let num_values = reader.read_u16::<BigEndian>()?;
-            let mut values = Vec::with_capacity(num_values.into());
+            let mut values = Vec::with_capacity(bounded_capacity(num_values.into()));
             for _ in 0..num_values {
                 values.push(read_annotations_element_value(class_file_path, reader)?);
             }
@@ -2072,7 +2530,7 @@ fn read_annotations_element_value(class_file_path: &str, reader: &mut BufReader<
     }
 }
 
-fn read_runtime_type_annotations_entry(class_file_path: &str, reader: &mut BufReader<File>) -> Result<AttributeRuntimeTypeAnnotationsEntry, Box<dyn error::Error>> {
+fn read_runtime_type_annotations_entry(class_file_path: &str, reader: &mut impl Read) -> Result<AttributeRuntimeTypeAnnotationsEntry, Box<dyn error::Error>> {
     let target_type = reader.read_u8()?;
     let target_info;
     match target_type {
@@ -2104,7 +2562,7 @@ fn read_runtime_type_annotations_entry(class_file_path: &str, reader: &mut BufRe
         },
         0x40 | 0x41 => {
             let table_length = reader.read_u16::<BigEndian>()?;
-            let mut table = Vec::with_capacity(table_length.into());
+            let mut table = Vec::with_capacity(bounded_capacity(table_length.into()));
             for _ in 0..table_length {
                 table.push(AttributeRuntimeTypeAnnotationsEntryLocalvarTargetTableEntry {
                     start_pc: reader.read_u16::<BigEndian>()?,
@@ -2135,7 +2593,7 @@ fn read_runtime_type_annotations_entry(class_file_path: &str, reader: &mut BufRe
     };
 
     let path_length = reader.read_u8()?;
-    let mut target_path = Vec::with_capacity(path_length.into());
+    let mut target_path = Vec::with_capacity(bounded_capacity(path_length.into()));
     for _ in 0..path_length {
         target_path.push(AttributeRuntimeVisibleTypeAnnotationsEntryTargetPathEntry {
             type_path_kind: reader.read_u8()?,
@@ -2146,7 +2604,7 @@ fn read_runtime_type_annotations_entry(class_file_path: &str, reader: &mut BufRe
     let type_index = reader.read_u16::<BigEndian>()?;
 
     let num_element_value_pairs = reader.read_u16::<BigEndian>()?;
-    let mut element_value_pairs = Vec::with_capacity(num_element_value_pairs.into());
+    let mut element_value_pairs = Vec::with_capacity(bounded_capacity(num_element_value_pairs.into()));
     for _ in 0..num_element_value_pairs {
         element_value_pairs.push(read_annotations_element_value_pair(class_file_path, reader)?);
     }
@@ -2160,22 +2618,22 @@ fn read_runtime_type_annotations_entry(class_file_path: &str, reader: &mut BufRe
     });
 }
 
-fn read_methods(class_file_path: &str, reader: &mut BufReader<File>, methods_count: u16, constant_pool: &Vec<CpInfo>) -> Result<Vec<MethodInfo>, Box<dyn error::Error>> {
-    let mut methods = Vec::with_capacity(methods_count.into());
+fn read_methods(class_file_path: &str, reader: &mut impl Read, methods_count: u16, constant_pool: &Vec<CpInfo>, options: &ParseOptions) -> Result<Vec<MethodInfo>, Box<dyn error::Error>> {
+    let mut methods = Vec::with_capacity(bounded_capacity(methods_count.into()));
     for _ in 0..methods_count {
-        methods.push(read_method(class_file_path, reader, constant_pool)?);
+        methods.push(read_method(class_file_path, reader, constant_pool, options)?);
     }
 
     return Ok(methods);
 }
 
-fn read_method(class_file_path: &str, reader: &mut BufReader<File>, constant_pool: &Vec<CpInfo>) -> Result<MethodInfo, Box<dyn error::Error>> {
+fn read_method(class_file_path: &str, reader: &mut impl Read, constant_pool: &Vec<CpInfo>, options: &ParseOptions) -> Result<MethodInfo, Box<dyn error::Error>> {
     let access_flags = reader.read_u16::<BigEndian>()?;
     let name_index = reader.read_u16::<BigEndian>()?;
     let descriptor_index = reader.read_u16::<BigEndian>()?;
 
     let attributes_count = reader.read_u16::<BigEndian>()?;
-    let attributes = read_attributes(class_file_path, reader, attributes_count, constant_pool)?;
+    let attributes = read_attributes(class_file_path, reader, attributes_count, constant_pool, options)?;
 
     return Ok(MethodInfo {
         access_flags,
@@ -2185,6 +2643,294 @@ fn read_method(class_file_path: &str, reader: &mut BufReader<File>, constant_poo
     })
 }
 
+/** Vnos constant poola v ClassFileRef. Utf8 vnosi si izposodijo bajte iz vhoda, ostali vnosi so enaki kot v ClassFile. */
+#[derive(Debug)]
+pub enum CpInfoRef<'a> {
+    /** Neprevedeni bajti v modified UTF-8 */
+    Utf8(&'a [u8]),
+    Other(CpInfo),
+    /** Indeks 0 in drugi indeks CpLong oz. CpDouble, ki nista veljavna vnosa */
+    Unusable,
+}
+
+/** Nerazčlenjen atribut. info vsebuje vseh attribute_length bajtov, ki sledijo glavi atributa. */
+#[derive(Debug)]
+pub struct AttributeInfoRef<'a> {
+    pub attribute_name_index: u16,
+    pub info: &'a [u8],
+}
+
+#[derive(Debug)]
+pub struct FieldInfoRef<'a> {
+    pub access_flags: u16,
+    pub name_index: u16,
+    pub descriptor_index: u16,
+    pub attributes: Vec<AttributeInfoRef<'a>>,
+}
+
+/** Izposojena različica AttributeCode. Bytecode in gnezdeni atributi so rezine vhoda. */
+#[derive(Debug)]
+pub struct AttributeCodeRef<'a> {
+    pub max_stack: u16,
+    pub max_locals: u16,
+    pub code: &'a [u8],
+    pub exception_table: Vec<ExceptionTableEntry>,
+    pub attributes: Vec<AttributeInfoRef<'a>>,
+}
+
+#[derive(Debug)]
+pub struct MethodInfoRef<'a> {
+    pub access_flags: u16,
+    pub name_index: u16,
+    pub descriptor_index: u16,
+    pub attributes: Vec<AttributeInfoRef<'a>>,
+}
+
+/**
+ * Izposojena različica ClassFile za analizo brez kopiranja (npr. nad memory-mapped JAR-om). Utf8 vnosi in atributi so
+ * rezine vhoda. Atributi ostanejo nerazčlenjeni, razen Code, ki ga vrne MethodInfoRef::code kot AttributeCodeRef z
+ * bytecodom, izposojenim iz vhoda. Ostali se razčlenijo šele v ClassFileRef::to_owned.
+ */
+#[derive(Debug)]
+pub struct ClassFileRef<'a> {
+    pub magic: u32,
+    pub minor_version: u16,
+    pub major_version: u16,
+    pub constant_pool_count: u16,
+    /** Enako indeksiranje kot ClassFile::constant_pool */
+    pub constant_pool: Vec<CpInfoRef<'a>>,
+    pub access_flags: u16,
+    pub this_class: u16,
+    pub super_class: u16,
+    pub interfaces: Vec<u16>,
+    pub fields: Vec<FieldInfoRef<'a>>,
+    pub methods: Vec<MethodInfoRef<'a>>,
+    pub attributes: Vec<AttributeInfoRef<'a>>,
+}
+
+impl<'a> ClassFileRef<'a> {
+    /** Neprevedeni bajti CpUtf8 na danem indeksu. */
+    pub fn get_utf8_bytes(&self, index: u16) -> Option<&'a [u8]> {
+        match self.constant_pool.get(usize::from(index))? {
+            CpInfoRef::Utf8(bytes) => Some(bytes),
+            CpInfoRef::Other(_) | CpInfoRef::Unusable => None,
+        }
+    }
+
+    /** Vnos constant poola na danem indeksu ali None, če indeks ne obstaja ali ni veljaven vnos. */
+    pub fn get_constant(&self, index: u16) -> Option<&CpInfoRef<'a>> {
+        match self.constant_pool.get(usize::from(index))? {
+            CpInfoRef::Unusable => None,
+            entry => Some(entry),
+        }
+    }
+
+    /** Pretvori v ClassFile. Šele tu se prevedejo Utf8 vnosi in razčlenijo atributi, zato lahko pretvorba spodleti. */
+    pub fn to_owned(&self) -> Result<ClassFile, Box<dyn error::Error>> {
+        let mut constant_pool = Vec::with_capacity(self.constant_pool.len());
+        for entry in &self.constant_pool {
+            constant_pool.push(match entry {
+                CpInfoRef::Utf8(bytes) => CpInfo::Utf8(CpUtf8 { bytes: bytes.to_vec(), converted: modified_utf8_to_string(bytes)? }),
+                CpInfoRef::Other(cp_info) => cp_info.clone(),
+                // ClassFile na teh mestih hrani nadomestni vnos, da se indeksi ujemajo
+                CpInfoRef::Unusable => CpInfo::Integer(CpInteger { bytes: [0; 4] }),
+            });
+        }
+
+        let mut fields = Vec::with_capacity(self.fields.len());
+        for field in &self.fields {
+            fields.push(FieldInfo {
+                access_flags: field.access_flags,
+                name_index: field.name_index,
+                descriptor_index: field.descriptor_index,
+                attributes: attributes_ref_to_owned(&field.attributes, &constant_pool)?,
+            });
+        }
+
+        let mut methods = Vec::with_capacity(self.methods.len());
+        for method in &self.methods {
+            methods.push(MethodInfo {
+                access_flags: method.access_flags,
+                name_index: method.name_index,
+                descriptor_index: method.descriptor_index,
+                attributes: attributes_ref_to_owned(&method.attributes, &constant_pool)?,
+            });
+        }
+
+        let attributes = attributes_ref_to_owned(&self.attributes, &constant_pool)?;
+
+        return Ok(ClassFile {
+            magic: self.magic,
+            minor_version: self.minor_version,
+            major_version: self.major_version,
+            constant_pool_count: self.constant_pool_count,
+            constant_pool,
+            access_flags: self.access_flags,
+            this_class: self.this_class,
+            super_class: self.super_class,
+            interfaces: self.interfaces.clone(),
+            fields,
+            methods,
+            attributes,
+        });
+    }
+}
+
+impl<'a> MethodInfoRef<'a> {
+    /**
+     * Code atribut metode brez kopiranja bytecoda oz. None za abstraktne in native metode. Napaka pomeni, da vsebina
+     * atributa ni veljavna ali ne obsega natanko attribute_length bajtov.
+     */
+    pub fn code(&self, class_file: &ClassFileRef<'a>) -> Result<Option<AttributeCodeRef<'a>>, Box<dyn error::Error>> {
+        let Some(code_attribute) = self.attributes.iter().find(|attribute| class_file.get_utf8_bytes(attribute.attribute_name_index) == Some(AttributeCode::ATTRIBUTE_NAME.as_bytes())) else {
+            return Ok(None);
+        };
+
+        let mut reader = Cursor::new(code_attribute.info);
+        let max_stack = reader.read_u16::<BigEndian>()?;
+        let max_locals = reader.read_u16::<BigEndian>()?;
+        let code_length = reader.read_u32::<BigEndian>()?;
+        let code = read_slice(&mut reader, usize::try_from(code_length)?)?;
+
+        let exception_table_length = reader.read_u16::<BigEndian>()?;
+        let mut exception_table = Vec::with_capacity(bounded_capacity(exception_table_length.into()));
+        for _ in 0..exception_table_length {
+            exception_table.push(ExceptionTableEntry {
+                start_pc: reader.read_u16::<BigEndian>()?,
+                end_pc: reader.read_u16::<BigEndian>()?,
+                handler_pc: reader.read_u16::<BigEndian>()?,
+                catch_type: reader.read_u16::<BigEndian>()?,
+            });
+        }
+
+        let attributes = read_attributes_ref(&mut reader)?;
+        if reader.position() != code_attribute.info.len() as u64 {
+            return Err(MalformedClassFile { file_path: STREAM_FILE_PATH.into(), msg: format!("atribut Code ima attribute_length {}, njegova vsebina pa obsega {} bajtov", code_attribute.info.len(), reader.position()) }.into());
+        }
+
+        return Ok(Some(AttributeCodeRef { max_stack, max_locals, code, exception_table, attributes }));
+    }
+}
+
+fn attributes_ref_to_owned(attributes: &[AttributeInfoRef], constant_pool: &Vec<CpInfo>) -> Result<Vec<AttributeInfo>, Box<dyn error::Error>> {
+    let mut owned_attributes = Vec::with_capacity(attributes.len());
+    for attribute in attributes {
+        let attribute_length = u32::try_from(attribute.info.len())?;
+        let mut reader = Cursor::new(attribute.info);
+        let owned_attribute = read_attribute_info(STREAM_FILE_PATH, &mut reader, attribute.attribute_name_index, attribute_length, constant_pool, &ParseOptions::default())?;
+        check_attribute_consumed(STREAM_FILE_PATH, &owned_attribute, attribute_length, reader.position())?;
+        owned_attributes.push(owned_attribute);
+    }
+
+    return Ok(owned_attributes);
+}
+
+/** Razčleni class v ClassFileRef, ki si izposodi Utf8 vnose in atribute iz bytes. */
+pub fn parse_class_file_ref<'a>(bytes: &'a [u8]) -> Result<ClassFileRef<'a>, Box<dyn error::Error>> {
+    let mut reader = Cursor::new(bytes);
+
+    let magic = reader.read_u32::<BigEndian>()?;
+    if magic != MAGIC {
+        return Err(MalformedClassFile::entry_wrong_value(STREAM_FILE_PATH, "MAGIC", magic, MAGIC).into());
+    }
+
+    let minor_version = reader.read_u16::<BigEndian>()?;
+    let major_version = reader.read_u16::<BigEndian>()?;
+
+    let constant_pool_count = reader.read_u16::<BigEndian>()?;
+    check_constant_pool_count(STREAM_FILE_PATH, constant_pool_count)?;
+    let mut constant_pool = Vec::with_capacity(bounded_capacity(constant_pool_count.into()));
+    constant_pool.push(CpInfoRef::Unusable);
+    while constant_pool.len() < constant_pool_count.into() {
+        if bytes.get(reader.position() as usize) == Some(&CpUtf8::TAG) {
+            reader.read_u8()?;
+            let length = reader.read_u16::<BigEndian>()?;
+            constant_pool.push(CpInfoRef::Utf8(read_slice(&mut reader, length.into())?));
+            continue;
+        }
+
+        let entry = read_constant_pool_entry(STREAM_FILE_PATH, &mut reader)?;
+        let takes_two_entries = matches!(entry, CpInfo::Long(_) | CpInfo::Double(_));
+        constant_pool.push(CpInfoRef::Other(entry));
+        if takes_two_entries {
+            constant_pool.push(CpInfoRef::Unusable);
+        }
+    }
+    check_constant_pool_length(STREAM_FILE_PATH, constant_pool_count, constant_pool.len())?;
+
+    let access_flags = reader.read_u16::<BigEndian>()?;
+    let this_class = reader.read_u16::<BigEndian>()?;
+    let super_class = reader.read_u16::<BigEndian>()?;
+
+    let interfaces_count = reader.read_u16::<BigEndian>()?;
+    let interfaces = read_interfaces(&mut reader, interfaces_count)?;
+
+    let fields_count = reader.read_u16::<BigEndian>()?;
+    let mut fields = Vec::with_capacity(bounded_capacity(fields_count.into()));
+    for _ in 0..fields_count {
+        fields.push(FieldInfoRef {
+            access_flags: reader.read_u16::<BigEndian>()?,
+            name_index: reader.read_u16::<BigEndian>()?,
+            descriptor_index: reader.read_u16::<BigEndian>()?,
+            attributes: read_attributes_ref(&mut reader)?,
+        });
+    }
+
+    let methods_count = reader.read_u16::<BigEndian>()?;
+    let mut methods = Vec::with_capacity(bounded_capacity(methods_count.into()));
+    for _ in 0..methods_count {
+        methods.push(MethodInfoRef {
+            access_flags: reader.read_u16::<BigEndian>()?,
+            name_index: reader.read_u16::<BigEndian>()?,
+            descriptor_index: reader.read_u16::<BigEndian>()?,
+            attributes: read_attributes_ref(&mut reader)?,
+        });
+    }
+
+    let attributes = read_attributes_ref(&mut reader)?;
+
+    return Ok(ClassFileRef {
+        magic,
+        minor_version,
+        major_version,
+        constant_pool_count,
+        constant_pool,
+        access_flags,
+        this_class,
+        super_class,
+        interfaces,
+        fields,
+        methods,
+        attributes,
+    });
+}
+
+fn read_attributes_ref<'a>(reader: &mut Cursor<&'a [u8]>) -> Result<Vec<AttributeInfoRef<'a>>, Box<dyn error::Error>> {
+    let attributes_count = reader.read_u16::<BigEndian>()?;
+    let mut attributes = Vec::with_capacity(bounded_capacity(attributes_count.into()));
+    for _ in 0..attributes_count {
+        let attribute_name_index = reader.read_u16::<BigEndian>()?;
+        let attribute_length = reader.read_u32::<BigEndian>()?;
+        let info = read_slice(reader, usize::try_from(attribute_length)?)?;
+        attributes.push(AttributeInfoRef { attribute_name_index, info });
+    }
+
+    return Ok(attributes);
+}
+
+/** Vrne naslednjih length bajtov vhoda brez kopiranja. */
+fn read_slice<'a>(reader: &mut Cursor<&'a [u8]>, length: usize) -> Result<&'a [u8], Box<dyn error::Error>> {
+    let bytes: &'a [u8] = reader.get_ref();
+    let start = reader.position() as usize;
+    let end = match start.checked_add(length) {
+        Some(end) if end <= bytes.len() => end,
+        _ => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+    };
+    reader.set_position(end as u64);
+
+    return Ok(&bytes[start..end]);
+}
+
 // struct CpInfo {
 //     tag: u8,
 //     info: Vec<u8>
@@ -2295,4 +3041,430 @@ fn read_method(class_file_path: &str, reader: &mut BufReader<File>, constant_poo
 // const CP_TAG_DYNAMIC: u8 = 17;
 // const CP_TAG_INVOKE_DYNAMIC: u8 = 18;
 // const CP_TAG_MODULE: u8 = 19;
-// const CP_TAG_PACKAGE: u8 = 20;
\ No newline at end of file
+// const CP_TAG_PACKAGE: u8 = 20;
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::{u16s, ClassBuilder};
+
+    use super::*;
+
+    fn parse_bytes(bytes: Vec<u8>) -> Result<ClassFile, Box<dyn error::Error>> {
+        return parse_one(&mut Cursor::new(bytes));
+    }
+
+    // brez feature std (cargo test --no-default-features) je to edina pot do razreda
+    #[test]
+    fn parses_class_from_bytes() {
+        let class_file = parse_bytes(ClassBuilder::new("p/A").bytes()).unwrap();
+
+        assert_eq!(class_file.magic, 0xCAFEBABE);
+        assert_eq!(class_file.major_version, 52);
+        assert_eq!(class_file.this_class_name(), Some("p/A"));
+    }
+
+    // &[u8] je bralnik tudi brez std, CI ta test poganja še s --no-default-features
+    #[test]
+    fn parses_class_from_slice_and_leaves_rest() {
+        let bytes = [ClassBuilder::new("p/A").bytes(), vec![0xAB]].concat();
+        let mut reader = bytes.as_slice();
+
+        let class_file = parse_one(&mut reader).unwrap();
+
+        assert_eq!(class_file.this_class_name(), Some("p/A"));
+        assert_eq!(reader, [0xAB]);
+        assert!(parse_one(&mut reader).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn parses_class_from_file() {
+        let path = std::env::temp_dir().join(format!("diploma-{}.class", std::process::id()));
+        std::fs::write(&path, ClassBuilder::new("p/A").bytes()).unwrap();
+
+        let class_file = parse_class_file(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(class_file.unwrap(), parse_bytes(ClassBuilder::new("p/A").bytes()).unwrap());
+    }
+
+    #[test]
+    fn attribute_length_must_match_content() {
+        let mut builder = ClassBuilder::new("A");
+        let line_number_table = builder.attribute("LineNumberTable", &[&u16s(&[1, 0, 3])[..], &[0, 0]].concat());
+        let code = builder.code(0, 1, &[0xb1], &[], &[line_number_table]);
+        builder.method(0x0009, "m", "()V", &[code]);
+
+        assert!(parse_bytes(builder.bytes()).is_err());
+    }
+
+    #[test]
+    fn attributes_ref_to_owned_checks_attribute_length() {
+        let mut builder = ClassBuilder::new("A");
+        let source_file = builder.utf8("A.java");
+        let source_file = builder.attribute("SourceFile", &[&u16s(&[source_file])[..], &[0]].concat());
+        builder.class_attribute(source_file);
+        let bytes = builder.bytes();
+
+        let class_file_ref = parse_class_file_ref(&bytes).unwrap();
+        assert!(class_file_ref.to_owned().is_err());
+    }
+
+    #[test]
+    fn class_file_ref_marks_placeholder_slots() {
+        let mut builder = ClassBuilder::new("A");
+        let long_index = builder.long(5);
+        let bytes = builder.bytes();
+        let class_file_ref = parse_class_file_ref(&bytes).unwrap();
+
+        assert!(class_file_ref.get_constant(0).is_none());
+        assert!(matches!(class_file_ref.get_constant(long_index), Some(CpInfoRef::Other(CpInfo::Long(_)))));
+        assert!(class_file_ref.get_constant(long_index + 1).is_none());
+        assert!(class_file_ref.get_utf8_bytes(long_index + 1).is_none());
+        assert_eq!(class_file_ref.to_owned().unwrap(), builder.parse());
+    }
+
+    #[test]
+    fn class_file_ref_code_borrows_bytecode_from_input() {
+        let mut builder = ClassBuilder::new("A");
+        let line_number_table = builder.attribute("LineNumberTable", &u16s(&[1, 0, 7]));
+        let code = builder.code(1, 2, &[0x03, 0x3c, 0xb1], &[[0, 2, 2, 0]], &[line_number_table]);
+        builder.method(0x0009, "m", "()V", &[code]);
+        builder.method(0x0401, "a", "()V", &[]);
+        let bytes = builder.bytes();
+        // Code brez zadnjega bajta (attributes_count)
+        let broken_code = builder.code(0, 0, &[0xb1], &[], &[]);
+        let broken_code = builder.attribute("Code", &broken_code[6..broken_code.len() - 1]);
+        builder.method(0x0009, "broken", "()V", &[broken_code]);
+        let broken_bytes = builder.bytes();
+
+        let class_file_ref = parse_class_file_ref(&bytes).unwrap();
+        let code_ref = class_file_ref.methods[0].code(&class_file_ref).unwrap().unwrap();
+        let class_file = parse_bytes(bytes.clone()).unwrap();
+        let code = class_file.methods[0].code().unwrap();
+
+        assert!(bytes.as_ptr_range().contains(&code_ref.code.as_ptr()));
+        assert_eq!((code_ref.max_stack, code_ref.max_locals, code_ref.code), (code.max_stack, code.max_locals, &code.code[..]));
+        assert_eq!(code_ref.exception_table, code.exception_table);
+        assert_eq!(code_ref.attributes.len(), 1);
+        assert_eq!(class_file_ref.get_utf8_bytes(code_ref.attributes[0].attribute_name_index), Some(&b"LineNumberTable"[..]));
+        assert!(class_file_ref.methods[1].code(&class_file_ref).unwrap().is_none());
+        let broken_class_file_ref = parse_class_file_ref(&broken_bytes).unwrap();
+        assert!(broken_class_file_ref.methods[2].code(&broken_class_file_ref).is_err());
+    }
+
+    #[test]
+    fn fixed_length_attribute_with_wrong_length_is_rejected() {
+        let mut builder = ClassBuilder::new("A");
+        let value = builder.integer(1);
+        let constant_value = builder.attribute("ConstantValue", &[&u16s(&[value])[..], &[0]].concat());
+        builder.field(0x0018, "X", "I", &[constant_value]);
+
+        let err = parse_bytes(builder.bytes()).unwrap_err();
+
+        assert!(err.to_string().contains("atribut ConstantValue ima attribute_length 3, pričakovan je 2"), "{}", err);
+    }
+
+    #[test]
+    fn attribute_name_index_out_of_range_is_error() {
+        let mut builder = ClassBuilder::new("A");
+        let out_of_range = builder.utf8("A.java") + 1;
+        builder.class_attribute([&u16s(&[out_of_range])[..], &0_u32.to_be_bytes()].concat());
+        let bytes = builder.bytes();
+
+        let err = parse_bytes(bytes.clone()).unwrap_err();
+
+        assert!(err.to_string().contains(&format!("attribute_name_index {} je izven constant pool", out_of_range)), "{}", err);
+        assert!(parse_class_file_ref(&bytes).unwrap().to_owned().is_err());
+    }
+
+    #[test]
+    fn attribute_name_index_to_non_utf8_is_error() {
+        let mut builder = ClassBuilder::new("A");
+        let this_class = builder.this_class;
+        builder.class_attribute([&u16s(&[this_class])[..], &0_u32.to_be_bytes()].concat());
+
+        let err = parse_bytes(builder.bytes()).unwrap_err();
+
+        assert!(err.to_string().contains(&format!("attribute_name_index {} ne vodi to CpUtf8", this_class)), "{}", err);
+    }
+
+    #[test]
+    fn local_variable_type_table_is_parsed_by_its_name() {
+        let mut builder = ClassBuilder::new("A");
+        let name = builder.utf8("list");
+        let signature = builder.utf8("Ljava/util/List<Ljava/lang/String;>;");
+        let local_variable_type_table = builder.attribute("LocalVariableTypeTable", &u16s(&[1, 0, 1, name, signature, 0]));
+        let code = builder.code(0, 1, &[0xb1], &[], &[local_variable_type_table]);
+        builder.method(0x0008, "m", "(Ljava/util/List;)V", &[code]);
+
+        let class_file = parse_bytes(builder.bytes()).unwrap();
+
+        let code = class_file.methods[0].code().unwrap();
+        assert_eq!(AttributeLocalVariableTypeTable::ATTRIBUTE_NAME, "LocalVariableTypeTable");
+        assert_eq!(code.attributes[0], AttributeInfo::LocalVariableTypeTable(AttributeLocalVariableTypeTable {
+            attribute_length: 12,
+            local_variable_table: vec![AttributeLocalVariableTypeTableEntry { start_pc: 0, length: 1, name_index: name, signature_index: signature, index: 0 }],
+        }));
+        assert_eq!(code.attributes[0].name(), "LocalVariableTypeTable");
+    }
+
+    #[test]
+    fn parse_one_leaves_reader_after_class() {
+        let first = ClassBuilder::new("p/First").bytes();
+        let second = ClassBuilder::new("p/Second").bytes();
+        let mut reader = Cursor::new([&first[..], &second, &[0xAB]].concat());
+
+        let first_class = parse_one(&mut reader).unwrap();
+        assert_eq!(reader.position(), first.len() as u64);
+        let second_class = parse_one(&mut reader).unwrap();
+        assert_eq!(reader.position(), (first.len() + second.len()) as u64);
+
+        assert_eq!(first_class.this_class_name(), Some("p/First"));
+        assert_eq!(second_class.this_class_name(), Some("p/Second"));
+        assert!(parse_one(&mut reader).is_err());
+    }
+
+    #[test]
+    fn parse_one_rejects_wrong_magic_and_truncated_class() {
+        let bytes = ClassBuilder::new("A").bytes();
+
+        let err = parse_bytes([&[0xCA, 0xFE, 0xBA, 0xBF][..], &bytes[4..]].concat()).unwrap_err();
+        assert!(err.to_string().contains("MAGIC"), "{}", err);
+        assert!(parse_bytes(bytes[..bytes.len() - 1].to_vec()).is_err());
+    }
+
+    /** Class z metodo, katere Code ima StackMapTable z vsebino stack_map_table, za njim pa še LineNumberTable. */
+    fn class_with_stack_map_table(stack_map_table: &[u8]) -> ClassBuilder {
+        let mut builder = ClassBuilder::new("A");
+        let stack_map_table = builder.attribute("StackMapTable", stack_map_table);
+        let line_number_table = builder.attribute("LineNumberTable", &u16s(&[1, 0, 7]));
+        let code = builder.code(0, 4, &[0x00; 8], &[], &[stack_map_table, line_number_table]);
+        builder.method(0x0009, "m", "()V", &[code]);
+
+        return builder;
+    }
+
+    #[test]
+    fn append_frame_reads_implied_locals() {
+        let class_file = class_with_stack_map_table(&[0, 1, 253, 0, 2, 1, 4]).parse();
+
+        let AttributeInfo::StackMapTable(attribute_stack_map_table) = &class_file.methods[0].code().unwrap().attributes[0] else {
+            panic!("pričakovan StackMapTable");
+        };
+        assert_eq!(attribute_stack_map_table.entries, [StackMapFrame::AppendFrame(StackMapAppendFrame {
+            frame_type: 253,
+            offset_delta: 2,
+            locals: vec![VerificationTypeInfo::IntegerVariable(VerificationTypeIntegerVariableInfo {}), VerificationTypeInfo::LongVariable(VerificationTypeLongVariableInfo {})],
+        })]);
+    }
+
+    #[test]
+    fn truncated_append_frame_is_error() {
+        let err = parse_bytes(class_with_stack_map_table(&[0, 1, 254, 0, 2, 1, 4, 9]).bytes()).unwrap_err();
+
+        assert!(err.to_string().contains("AppendFrame s frame_type 254 je okrnjen, lokalne spremenljivke 3 od 3 ni mogoče prebrati"), "{}", err);
+    }
+
+    #[test]
+    fn verification_type_tags_round_trip() {
+        for tag in 0..=8 {
+            match VerificationTypeInfo::from_tag(tag) {
+                Some(verification_type) => assert_eq!(verification_type.tag(), tag),
+                None => assert!(tag == VerificationTypeObjectVariableInfo::TAG || tag == VerificationTypeUninitializedVariableInfo::TAG, "{}", tag),
+            }
+        }
+
+        assert_eq!(VerificationTypeInfo::object(3).tag(), 7);
+        assert_eq!(VerificationTypeInfo::uninitialized(3).tag(), 8);
+        assert_eq!(VerificationTypeInfo::from_tag(9), None);
+    }
+
+    #[test]
+    fn unknown_verification_type_tag_is_error() {
+        let err = parse_bytes(class_with_stack_map_table(&[0, 1, 64, 9]).bytes()).unwrap_err();
+
+        assert!(err.to_string().contains("Neznan VerificationTypeInfo tag: 0x9"), "{}", err);
+    }
+
+    #[test]
+    fn lenient_parsing_skips_rest_of_malformed_stack_map_table() {
+        // SameFrame, nato rezerviran frame_type 200 in še en SameFrame, ki se ne prebere
+        let builder = class_with_stack_map_table(&[0, 3, 1, 200, 2]);
+        let options = ParseOptions { lenient: true, ..ParseOptions::default() };
+
+        let class_file = parse_one_with_options(&mut Cursor::new(builder.bytes()), &options).unwrap();
+
+        let code = class_file.methods[0].code().unwrap();
+        let AttributeInfo::StackMapTable(attribute_stack_map_table) = &code.attributes[0] else {
+            panic!("pričakovan StackMapTable");
+        };
+        assert_eq!(attribute_stack_map_table.entries, [StackMapFrame::SameFrame(StackMapSameFrame { frame_type: 1 })]);
+        assert!(attribute_stack_map_table.error.is_some());
+        assert_eq!(code.attributes[1].name(), "LineNumberTable");
+        assert!(parse_bytes(builder.bytes()).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parse_bytes_to_json_serializes_class() {
+        let mut builder = ClassBuilder::new("p/A");
+        builder.field(0x0002, "x", "I", &[]);
+
+        let json: serde_json::Value = serde_json::from_str(&parse_bytes_to_json(&builder.bytes()).unwrap()).unwrap();
+
+        assert_eq!(json["magic"], 0xCAFEBABE_u32);
+        assert_eq!(json["major_version"], 52);
+        assert_eq!(json["this_class"], builder.this_class);
+        assert_eq!(json["fields"].as_array().unwrap().len(), 1);
+        assert_eq!(json["constant_pool"].as_array().unwrap().len(), builder.parse().constant_pool.len());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parse_bytes_to_json_returns_error_message() {
+        let err = parse_bytes_to_json(&[0xCA, 0xFE]).unwrap_err();
+
+        assert!(!err.is_empty());
+    }
+
+    #[test]
+    fn max_constant_pool_entries_is_enforced() {
+        let builder = ClassBuilder::new("A");
+        let constant_pool_count = builder.parse().constant_pool.len() as u16;
+        let parse_with_limit = |max_constant_pool_entries| {
+            let options = ParseOptions { max_constant_pool_entries, ..ParseOptions::default() };
+            return parse_one_with_options(&mut Cursor::new(builder.bytes()), &options);
+        };
+
+        assert!(parse_with_limit(constant_pool_count).is_ok());
+        let err = parse_with_limit(constant_pool_count - 1).unwrap_err();
+        assert!(err.to_string().contains(&format!("constant_pool_count {} presega omejitev {}", constant_pool_count, constant_pool_count - 1)), "{}", err);
+    }
+
+    #[test]
+    fn zero_constant_pool_count_is_error() {
+        let bytes = [&0xCAFEBABE_u32.to_be_bytes()[..], &u16s(&[0, 52, 0])].concat();
+
+        let err = parse_bytes(bytes).unwrap_err();
+
+        assert!(err.to_string().contains("constant_pool_count je 0"), "{}", err);
+    }
+
+    #[test]
+    fn long_in_last_constant_pool_slot_is_error() {
+        let mut builder = ClassBuilder::new("A");
+        let long_index = builder.long(1);
+        let mut bytes = builder.bytes();
+        // constant_pool_count brez drugega vnosa Long
+        bytes[8..10].copy_from_slice(&(long_index + 1).to_be_bytes());
+
+        let err = parse_bytes(bytes.clone()).unwrap_err();
+
+        assert!(err.to_string().contains(&format!("Long ali Double na indeksu {} presega constant_pool_count {}", long_index, long_index + 1)), "{}", err);
+        assert!(parse_class_file_ref(&bytes).is_err());
+    }
+
+    #[test]
+    fn empty_tables_at_boundaries() {
+        let mut builder = ClassBuilder::new("A");
+        builder.access_flags = 0x0421;
+        let bytes = builder.bytes();
+
+        let class_file = parse_bytes(bytes.clone()).unwrap();
+        let class_file_ref = parse_class_file_ref(&bytes).unwrap();
+
+        assert!(class_file.interfaces.is_empty() && class_file.fields.is_empty() && class_file.methods.is_empty() && class_file.attributes.is_empty());
+        assert_eq!(class_file_ref.to_owned().unwrap(), class_file);
+        assert!(parse_class_file_ref(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn huge_lengths_in_short_input_fail_without_preallocating() {
+        // kapaciteta je omejena ne glede na prebrano dolžino ali število
+        assert_eq!(bounded_capacity(u32::MAX), MAX_PREALLOCATED_CAPACITY);
+        assert_eq!(bounded_capacity(3), 3);
+
+        let mut builder = ClassBuilder::new("p/A");
+        let code = builder.attribute("Code", &[&u16s(&[1, 1])[..], &u32::MAX.to_be_bytes()].concat());
+        builder.method(0x0009, "m", "()V", &[code]);
+        assert!(parse_bytes(builder.bytes()).is_err());
+
+        let mut builder = ClassBuilder::new("p/A");
+        let debug_extension = builder.utf8("SourceDebugExtension");
+        builder.class_attribute([&debug_extension.to_be_bytes()[..], &u32::MAX.to_be_bytes(), b"SMAP"].concat());
+        assert!(parse_bytes(builder.bytes()).is_err());
+
+        let mut bytes = ClassBuilder::new("p/A").bytes();
+        bytes[8..10].copy_from_slice(&0xFFFE_u16.to_be_bytes());
+        assert!(parse_bytes(bytes).is_err());
+    }
+
+    #[test]
+    fn empty_parameter_annotation_tables_keep_alignment_and_round_trip() {
+        let mut builder = ClassBuilder::new("p/A");
+        let annotation_type = builder.utf8("Lp/NotNull;");
+        // num_parameters 0
+        let no_parameters = builder.attribute("RuntimeVisibleParameterAnnotations", &[0]);
+        builder.method(0x0401, "a", "()V", &[no_parameters]);
+        // trije parametri, anotiran le srednji, anotacija brez parov element-vrednost
+        let middle_annotated = builder.attribute("RuntimeVisibleParameterAnnotations", &[&[3][..], &u16s(&[0, 1, annotation_type, 0, 0])].concat());
+        let all_empty = builder.attribute("RuntimeInvisibleParameterAnnotations", &[&[2][..], &u16s(&[0, 0])].concat());
+        builder.method(0x0401, "b", "(III)V", &[middle_annotated, all_empty]);
+        // num_annotations 0
+        let no_annotations = builder.attribute("RuntimeVisibleAnnotations", &u16s(&[0]));
+        builder.class_attribute(no_annotations);
+        let bytes = builder.bytes();
+        let class_file = parse_bytes(bytes.clone()).unwrap();
+
+        let AttributeInfo::RuntimeVisibleParameterAnnotations(no_parameters) = &class_file.methods[0].attributes[0] else {
+            panic!("pričakovan RuntimeVisibleParameterAnnotations");
+        };
+        assert!(no_parameters.parameter_annotations.is_empty());
+
+        let AttributeInfo::RuntimeVisibleParameterAnnotations(middle_annotated) = &class_file.methods[1].attributes[0] else {
+            panic!("pričakovan RuntimeVisibleParameterAnnotations");
+        };
+        let lengths: Vec<_> = middle_annotated.parameter_annotations.iter().map(Vec::len).collect();
+        assert_eq!(lengths, [0, 1, 0]);
+        assert_eq!(middle_annotated.parameter_annotations[1][0].type_index, annotation_type);
+        assert!(middle_annotated.parameter_annotations[1][0].element_value_pairs.is_empty());
+
+        let AttributeInfo::RuntimeInvisibleParameterAnnotations(all_empty) = &class_file.methods[1].attributes[1] else {
+            panic!("pričakovan RuntimeInvisibleParameterAnnotations");
+        };
+        assert_eq!(all_empty.parameter_annotations, [Vec::new(), Vec::new()]);
+
+        let AttributeInfo::RuntimeVisibleAnnotations(no_annotations) = &class_file.attributes[0] else {
+            panic!("pričakovan RuntimeVisibleAnnotations");
+        };
+        assert!(no_annotations.annotations.is_empty());
+
+        assert_eq!(class_file.to_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn skip_attributes_leaves_reader_at_next_structure() {
+        let mut builder = ClassBuilder::new("p/A");
+        let first = builder.attribute("Synthetic", &[]);
+        let second = builder.attribute("Unknown", &[1, 2, 3, 4, 5]);
+        let bytes = [first, second, u16s(&[0xBEEF])].concat();
+        let mut reader = bytes.as_slice();
+
+        skip_attributes(&mut reader, 2).unwrap();
+
+        assert_eq!(reader.read_u16::<BigEndian>().unwrap(), 0xBEEF);
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn skip_attributes_with_truncated_content_is_error() {
+        // attribute_length 10, vsebina pa le 3 bajti
+        let bytes = [&u16s(&[1])[..], &10_u32.to_be_bytes(), &[1, 2, 3]].concat();
+        assert!(skip_attributes(&mut bytes.as_slice(), 1).is_err());
+
+        // attribute_length odrezan na 2 bajta
+        let bytes = [&u16s(&[1])[..], &[0, 0]].concat();
+        assert!(skip_attributes(&mut bytes.as_slice(), 1).is_err());
+    }
+}
@@ -0,0 +1,339 @@
+use alloc::{collections::BTreeSet, vec::Vec};
+
+use crate::{disassembler::{disassemble, MalformedBytecode}, parser::{AttributeAnnotationsElementValue, AttributeInfo, AttributeRuntimeAnnotationsEntry, ClassFile, CpInfo, StackMapFrame, VerificationTypeInfo}};
+
+impl ClassFile {
+    /**
+     * Indeksi vnosov constant poola, na katere ne kaže nobena referenca v classu (neposredno ali preko drugih vnosov).
+     * Indeks 0 in drugi indeksi CpLong oz. CpDouble niso nikoli vključeni. Ker parser indeksov imen atributov ne shranjuje,
+     * se kot uporabljeni štejejo vsi CpUtf8 z imenom katerega od prisotnih atributov. Če bytecoda katere od metod ni mogoče
+     * razčleniti, ni znano, na katere vnose kaže, zato funkcija vrne napako.
+     */
+    pub fn unreferenced_constants(&self) -> Result<Vec<u16>, MalformedBytecode> {
+        let referenced = self.referenced_constants()?;
+
+        let mut unreferenced = Vec::new();
+        for index in 1..self.constant_pool.len() {
+            // drugi indeks CpLong oz. CpDouble
+            if matches!(self.constant_pool[index - 1], CpInfo::Long(_) | CpInfo::Double(_)) {
+                continue;
+            }
+
+            if !referenced.contains(&(index as u16)) {
+                unreferenced.push(index as u16);
+            }
+        }
+
+        return Ok(unreferenced);
+    }
+
+    /** Indeksi vseh vnosov constant poola, ki so dosegljivi iz strukture classa. */
+    fn referenced_constants(&self) -> Result<BTreeSet<u16>, MalformedBytecode> {
+        let mut references = References::default();
+        references.add(self.this_class);
+        references.add(self.super_class);
+        for &interface in &self.interfaces {
+            references.add(interface);
+        }
+        for field in &self.fields {
+            references.add(field.name_index);
+            references.add(field.descriptor_index);
+            references.add_attributes(&field.attributes)?;
+        }
+        for method in &self.methods {
+            references.add(method.name_index);
+            references.add(method.descriptor_index);
+            references.add_attributes(&method.attributes)?;
+        }
+        references.add_attributes(&self.attributes)?;
+
+        for (index, entry) in self.constant_pool.iter().enumerate().skip(1) {
+            if let CpInfo::Utf8(cp_utf8) = entry && references.attribute_names.contains(cp_utf8.converted.as_str()) {
+                references.add(index as u16);
+            }
+        }
+
+        // vnosi constant poola kažejo na druge vnose, zato se doda še vse, kar je dosegljivo iz že najdenih
+        let mut pending: Vec<u16> = references.indices.iter().copied().collect();
+        while let Some(index) = pending.pop() {
+            let Some(entry) = self.get_constant(index) else {
+                continue;
+            };
+
+            let referenced_indices = match entry {
+                CpInfo::Class(cp_class) => vec![cp_class.name_index],
+                CpInfo::String(cp_string) => vec![cp_string.string_index],
+                CpInfo::FieldRef(cp_ref) => vec![cp_ref.class_index, cp_ref.name_and_type_index],
+                CpInfo::MethodRef(cp_ref) => vec![cp_ref.class_index, cp_ref.name_and_type_index],
+                CpInfo::InterfaceMethodRef(cp_ref) => vec![cp_ref.class_index, cp_ref.name_and_type_index],
+                CpInfo::NameAndType(cp_name_and_type) => vec![cp_name_and_type.name_index, cp_name_and_type.descriptor_index],
+                CpInfo::MethodHandle(cp_method_handle) => vec![cp_method_handle.reference_index],
+                CpInfo::MethodType(cp_method_type) => vec![cp_method_type.descriptor_index],
+                CpInfo::Dynamic(cp_dynamic) => vec![cp_dynamic.name_and_type_index],
+                CpInfo::InvokeDynamic(cp_invoke_dynamic) => vec![cp_invoke_dynamic.name_and_type_index],
+                CpInfo::Module(cp_module) => vec![cp_module.name_index],
+                CpInfo::Package(cp_package) => vec![cp_package.name_index],
+                _ => vec![],
+            };
+            for referenced_index in referenced_indices {
+                if referenced_index != 0 && references.indices.insert(referenced_index) {
+                    pending.push(referenced_index);
+                }
+            }
+        }
+
+        return Ok(references.indices);
+    }
+}
+
+#[derive(Default)]
+struct References {
+    indices: BTreeSet<u16>,
+    attribute_names: BTreeSet<&'static str>,
+}
+
+impl References {
+    /** Indeks 0 pomeni, da reference ni (npr. super_class pri java/lang/Object), zato se ne doda. */
+    fn add(&mut self, index: u16) {
+        if index != 0 {
+            self.indices.insert(index);
+        }
+    }
+
+    fn add_attributes(&mut self, attributes: &[AttributeInfo]) -> Result<(), MalformedBytecode> {
+        for attribute in attributes {
+            self.attribute_names.insert(attribute.name());
+            match attribute {
+                AttributeInfo::ConstantValue(attribute_constant_value) => self.add(attribute_constant_value.constantvalue_index),
+                AttributeInfo::Code(attribute_code) => {
+                    for instruction in disassemble(&attribute_code.code)? {
+                        if let Some(index) = instruction.constant_pool_index() {
+                            self.add(index);
+                        }
+                    }
+                    for entry in &attribute_code.exception_table {
+                        self.add(entry.catch_type);
+                    }
+                    self.add_attributes(&attribute_code.attributes)?;
+                },
+                AttributeInfo::StackMapTable(attribute_stack_map_table) => {
+                    for frame in &attribute_stack_map_table.entries {
+                        let verification_types = match frame {
+                            StackMapFrame::SameLocals1StackItemFrame(frame) => core::slice::from_ref(&frame.stack_entry),
+                            StackMapFrame::SameLocals1StackItemFrameExtended(frame) => core::slice::from_ref(&frame.stack_entry),
+                            StackMapFrame::AppendFrame(frame) => frame.locals.as_slice(),
+                            StackMapFrame::FullFrame(frame) => {
+                                self.add_verification_types(&frame.locals);
+                                frame.stack.as_slice()
+                            },
+                            _ => &[],
+                        };
+                        self.add_verification_types(verification_types);
+                    }
+                },
+                AttributeInfo::Exceptions(attribute_exceptions) => {
+                    for &index in &attribute_exceptions.exception_index_table {
+                        self.add(index);
+                    }
+                },
+                AttributeInfo::InnerClasses(attribute_inner_classes) => {
+                    for class in &attribute_inner_classes.classes {
+                        self.add(class.inner_class_info_index);
+                        self.add(class.outer_class_info_index);
+                        self.add(class.inner_name_index);
+                    }
+                },
+                AttributeInfo::EnclosingMethod(attribute_enclosing_method) => {
+                    self.add(attribute_enclosing_method.class_index);
+                    self.add(attribute_enclosing_method.method_index);
+                },
+                AttributeInfo::Signature(attribute_signature) => self.add(attribute_signature.signature_index),
+                AttributeInfo::SourceFile(attribute_source_file) => self.add(attribute_source_file.sourcefile_index),
+                AttributeInfo::LocalVariableTable(attribute_local_variable_table) => {
+                    for entry in &attribute_local_variable_table.local_variable_table {
+                        self.add(entry.name_index);
+                        self.add(entry.descriptor_index);
+                    }
+                },
+                AttributeInfo::LocalVariableTypeTable(attribute_local_variable_type_table) => {
+                    for entry in &attribute_local_variable_type_table.local_variable_table {
+                        self.add(entry.name_index);
+                        self.add(entry.signature_index);
+                    }
+                },
+                AttributeInfo::RuntimeVisibleAnnotations(attribute_annotations) => self.add_annotations(&attribute_annotations.annotations),
+                AttributeInfo::RuntimeInvisibleAnnotations(attribute_annotations) => self.add_annotations(&attribute_annotations.annotations),
+                AttributeInfo::RuntimeVisibleParameterAnnotations(attribute_parameter_annotations) => {
+                    for annotations in &attribute_parameter_annotations.parameter_annotations {
+                        self.add_annotations(annotations);
+                    }
+                },
+                AttributeInfo::RuntimeInvisibleParameterAnnotations(attribute_parameter_annotations) => {
+                    for annotations in &attribute_parameter_annotations.parameter_annotations {
+                        self.add_annotations(annotations);
+                    }
+                },
+                AttributeInfo::RuntimeVisibleTypeAnnotations(attribute_type_annotations) => {
+                    for annotation in &attribute_type_annotations.annotations {
+                        self.add(annotation.type_index);
+                        for pair in &annotation.element_value_pairs {
+                            self.add(pair.element_name_index);
+                            self.add_element_value(&pair.element_value);
+                        }
+                    }
+                },
+                AttributeInfo::RuntimeInvisibleTypeAnnotations(attribute_type_annotations) => {
+                    for annotation in &attribute_type_annotations.annotations {
+                        self.add(annotation.type_index);
+                        for pair in &annotation.element_value_pairs {
+                            self.add(pair.element_name_index);
+                            self.add_element_value(&pair.element_value);
+                        }
+                    }
+                },
+                AttributeInfo::AnnotationDefault(attribute_annotation_default) => self.add_element_value(&attribute_annotation_default.default_value),
+                AttributeInfo::BootstrapMethods(attribute_bootstrap_methods) => {
+                    for bootstrap_method in &attribute_bootstrap_methods.bootstrap_methods {
+                        self.add(bootstrap_method.bootstrap_method_ref);
+                        for &argument in &bootstrap_method.bootstrap_arguments {
+                            self.add(argument);
+                        }
+                    }
+                },
+                AttributeInfo::MethodParameters(attribute_method_parameters) => {
+                    for parameter in &attribute_method_parameters.parameters {
+                        self.add(parameter.name_index);
+                    }
+                },
+                AttributeInfo::Module(attribute_module) => {
+                    self.add(attribute_module.module_name_index);
+                    self.add(attribute_module.module_version_index);
+                    for requires in &attribute_module.requires {
+                        self.add(requires.requires_index);
+                        self.add(requires.requires_version_index);
+                    }
+                    for exports in &attribute_module.exports {
+                        self.add(exports.exports_index);
+                        for &index in &exports.exports_to_index {
+                            self.add(index);
+                        }
+                    }
+                    for opens in &attribute_module.opens {
+                        self.add(opens.opens_index);
+                        for &index in &opens.opens_to_index {
+                            self.add(index);
+                        }
+                    }
+                    for &index in &attribute_module.uses_index {
+                        self.add(index);
+                    }
+                    for provides in &attribute_module.provides {
+                        self.add(provides.provides_index);
+                        for &index in &provides.provides_with_index {
+                            self.add(index);
+                        }
+                    }
+                },
+                AttributeInfo::ModulePackages(attribute_module_packages) => {
+                    for &index in &attribute_module_packages.package_index {
+                        self.add(index);
+                    }
+                },
+                AttributeInfo::ModuleMainClass(attribute_module_main_class) => self.add(attribute_module_main_class.main_class_index),
+                AttributeInfo::NestHost(attribute_nest_host) => self.add(attribute_nest_host.host_class_index),
+                AttributeInfo::NestMembers(attribute_nest_members) => {
+                    for &index in &attribute_nest_members.classes {
+                        self.add(index);
+                    }
+                },
+                AttributeInfo::Record(attribute_record) => {
+                    for component in &attribute_record.components {
+                        self.add(component.name_index);
+                        self.add(component.descriptor_index);
+                        self.add_attributes(&component.attributes)?;
+                    }
+                },
+                AttributeInfo::PermittedSubclasses(attribute_permitted_subclasses) => {
+                    for &index in &attribute_permitted_subclasses.classes {
+                        self.add(index);
+                    }
+                },
+                AttributeInfo::Synthetic(_) | AttributeInfo::SourceDebugExtension(_) | AttributeInfo::LineNumberTable(_) | AttributeInfo::Deprecated(_) => (),
+            }
+        }
+
+        return Ok(());
+    }
+
+    fn add_verification_types(&mut self, verification_types: &[VerificationTypeInfo]) {
+        for verification_type in verification_types {
+            if let VerificationTypeInfo::ObjectVariable(object_variable) = verification_type {
+                self.add(object_variable.cpool_index);
+            }
+        }
+    }
+
+    fn add_annotations(&mut self, annotations: &[AttributeRuntimeAnnotationsEntry]) {
+        for annotation in annotations {
+            self.add(annotation.type_index);
+            for pair in &annotation.element_value_pairs {
+                self.add(pair.element_name_index);
+                self.add_element_value(&pair.element_value);
+            }
+        }
+    }
+
+    fn add_element_value(&mut self, element_value: &AttributeAnnotationsElementValue) {
+        match element_value {
+            AttributeAnnotationsElementValue::Byte(value) => self.add(value.const_value_index),
+            AttributeAnnotationsElementValue::Char(value) => self.add(value.const_value_index),
+            AttributeAnnotationsElementValue::Double(value) => self.add(value.const_value_index),
+            AttributeAnnotationsElementValue::Float(value) => self.add(value.const_value_index),
+            AttributeAnnotationsElementValue::Int(value) => self.add(value.const_value_index),
+            AttributeAnnotationsElementValue::Long(value) => self.add(value.const_value_index),
+            AttributeAnnotationsElementValue::Short(value) => self.add(value.const_value_index),
+            AttributeAnnotationsElementValue::Boolean(value) => self.add(value.const_value_index),
+            AttributeAnnotationsElementValue::String(value) => self.add(value.const_value_index),
+            AttributeAnnotationsElementValue::EnumClass(value) => {
+                self.add(value.type_name_index);
+                self.add(value.const_name_index);
+            },
+            AttributeAnnotationsElementValue::Class(value) => self.add(value.class_info_index),
+            AttributeAnnotationsElementValue::AnnotationInterface(value) => self.add_annotations(core::slice::from_ref(&value.annotation_value)),
+            AttributeAnnotationsElementValue::ArrayType(value) => {
+                for value in &value.values {
+                    self.add_element_value(value);
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::ClassBuilder;
+
+    #[test]
+    fn finds_unreferenced_constants() {
+        let mut builder = ClassBuilder::new("A");
+        let field_ref = builder.field_ref("A", "x", "I");
+        let unused_long = builder.long(1);
+        let unused_utf8 = builder.utf8("unused");
+        // getstatic A.x, pop, return
+        let code = builder.code(1, 0, &[0xb2, (field_ref >> 8) as u8, field_ref as u8, 0x57, 0xb1], &[], &[]);
+        builder.method(0x0008, "<clinit>", "()V", &[code]);
+
+        assert_eq!(builder.parse().unreferenced_constants().unwrap(), vec![unused_long, unused_utf8]);
+    }
+
+    #[test]
+    fn malformed_bytecode_is_error() {
+        let mut builder = ClassBuilder::new("A");
+        builder.utf8("unused");
+        // sipush brez operanda
+        let code = builder.code(1, 0, &[0x11, 0x00], &[], &[]);
+        builder.method(0x0008, "<clinit>", "()V", &[code]);
+
+        assert!(builder.parse().unreferenced_constants().is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use core::{error::Error, fmt};
+use alloc::{string::String, vec::Vec};
+
+use crate::parser::{AttributeCode, AttributeModule, ClassFile, CpInfo, ExceptionTableEntry, MethodHandleReferenceKind};
+
+/** Indeks, ki bi moral kazati na določeno vrsto vnosa v constant poolu, kaže drugam ali izven constant poola. */
+#[derive(Debug, Clone)]
+pub struct InvalidConstantPoolReference {
+    msg: String,
+}
+
+impl fmt::Display for InvalidConstantPoolReference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Neveljavna referenca v constant pool: {}.", self.msg)
+    }
+}
+
+impl Error for InvalidConstantPoolReference {}
+
+/** Razrešena referenca na polje ali metodo (CpFieldRef, CpMethodRef ali CpInterfaceMethodRef). */
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemberRef<'a> {
+    pub class_name: &'a str,
+    pub name: &'a str,
+    pub descriptor: &'a str,
+}
+
+impl fmt::Display for MemberRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}.{}:{}", self.class_name, self.name, self.descriptor)
+    }
+}
+
+/** Razrešen CpMethodHandle, npr. `invokestatic Foo.bar:(I)V`. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResolvedMethodHandle<'a> {
+    pub reference_kind: MethodHandleReferenceKind,
+    pub reference: MemberRef<'a>,
+}
+
+impl fmt::Display for ResolvedMethodHandle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.reference_kind.mnemonic(), self.reference)
+    }
+}
+
+/** Razrešena izjava `provides service with implementations;` iz Module atributa. */
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleProvides<'a> {
+    pub service: &'a str,
+    pub implementations: Vec<&'a str>,
+}
+
+impl MethodHandleReferenceKind {
+    /** Ime ukaza, ki ga method handle predstavlja (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-5.html#jvms-5.4.3.5) */
+    pub fn mnemonic(&self) -> &'static str {
+        match self {
+            MethodHandleReferenceKind::GetField => "getfield",
+            MethodHandleReferenceKind::GetStatic => "getstatic",
+            MethodHandleReferenceKind::PutField => "putfield",
+            MethodHandleReferenceKind::PutStatic => "putstatic",
+            MethodHandleReferenceKind::InvokeVirtual => "invokevirtual",
+            MethodHandleReferenceKind::InvokeStatic => "invokestatic",
+            MethodHandleReferenceKind::InvokeSpecial => "invokespecial",
+            MethodHandleReferenceKind::NewInvokeSpecial => "newinvokespecial",
+            MethodHandleReferenceKind::InvokeInterface => "invokeinterface",
+        }
+    }
+}
+
+impl ClassFile {
+    /**
+     * Vrne vnos constant poola na danem (1-indexed) indeksu ali None, če indeks ne obstaja. Tudi drugi indeks CpLong
+     * oz. CpDouble ni veljaven vnos, čeprav constant_pool na tem mestu hrani nadomestni CpInteger.
+     */
+    pub fn get_constant(&self, index: u16) -> Option<&CpInfo> {
+        let index = usize::from(index);
+        if index == 0 || matches!(self.constant_pool.get(index - 1), Some(CpInfo::Long(_) | CpInfo::Double(_))) {
+            return None;
+        }
+
+        return self.constant_pool.get(index);
+    }
+
+    pub fn get_utf8(&self, index: u16) -> Option<&str> {
+        match self.get_constant(index)? {
+            CpInfo::Utf8(cp_utf8) => Some(&cp_utf8.converted),
+            _ => None,
+        }
+    }
+
+    /** Vrednost string konstante (CpString) na danem indeksu. */
+    pub fn get_string(&self, index: u16) -> Option<&str> {
+        match self.get_constant(index)? {
+            CpInfo::String(cp_string) => self.get_utf8(cp_string.string_index),
+            _ => None,
+        }
+    }
+
+    /** Ime (internal form, npr. `java/lang/Object`) razreda, na katerega kaže CpClass na danem indeksu. */
+    pub fn get_class_name(&self, index: u16) -> Option<&str> {
+        match self.get_constant(index)? {
+            CpInfo::Class(cp_class) => self.get_utf8(cp_class.name_index),
+            _ => None,
+        }
+    }
+
+    pub fn this_class_name(&self) -> Option<&str> {
+        return self.get_class_name(self.this_class);
+    }
+
+    /** Vrne None tudi za java/lang/Object in module-info, ki nimata nadrazreda (super_class je 0). */
+    pub fn super_class_name(&self) -> Option<&str> {
+        return self.get_class_name(self.super_class);
+    }
+
+    /** Vrne (ime, deskriptor) iz CpNameAndType na danem indeksu. */
+    pub fn get_name_and_type(&self, index: u16) -> Option<(&str, &str)> {
+        match self.get_constant(index)? {
+            CpInfo::NameAndType(cp_name_and_type) => Some((self.get_utf8(cp_name_and_type.name_index)?, self.get_utf8(cp_name_and_type.descriptor_index)?)),
+            _ => None,
+        }
+    }
+
+    /** Razreši CpFieldRef, CpMethodRef ali CpInterfaceMethodRef v lastnika, ime in deskriptor. */
+    pub fn resolve_ref(&self, index: u16) -> Option<MemberRef<'_>> {
+        let (class_index, name_and_type_index) = match self.get_constant(index)? {
+            CpInfo::FieldRef(cp_ref) => (cp_ref.class_index, cp_ref.name_and_type_index),
+            CpInfo::MethodRef(cp_ref) => (cp_ref.class_index, cp_ref.name_and_type_index),
+            CpInfo::InterfaceMethodRef(cp_ref) => (cp_ref.class_index, cp_ref.name_and_type_index),
+            _ => return None,
+        };
+
+        let class_name = self.get_class_name(class_index)?;
+        let (name, descriptor) = self.get_name_and_type(name_and_type_index)?;
+
+        return Some(MemberRef { class_name, name, descriptor });
+    }
+
+    /**
+     * Razreši CpMethodHandle na danem indeksu. Vrne None, če reference_index ne kaže na vrsto reference,
+     * ki jo reference_kind dovoljuje (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.4.8).
+     */
+    pub fn resolve_method_handle(&self, index: u16) -> Option<ResolvedMethodHandle<'_>> {
+        let cp_method_handle = match self.get_constant(index)? {
+            CpInfo::MethodHandle(cp_method_handle) => cp_method_handle,
+            _ => return None,
+        };
+
+        let target = self.get_constant(cp_method_handle.reference_index)?;
+        let target_allowed = match cp_method_handle.reference_kind {
+            MethodHandleReferenceKind::GetField | MethodHandleReferenceKind::GetStatic |
+            MethodHandleReferenceKind::PutField | MethodHandleReferenceKind::PutStatic => matches!(target, CpInfo::FieldRef(_)),
+            MethodHandleReferenceKind::InvokeVirtual | MethodHandleReferenceKind::NewInvokeSpecial => matches!(target, CpInfo::MethodRef(_)),
+            MethodHandleReferenceKind::InvokeStatic | MethodHandleReferenceKind::InvokeSpecial => matches!(target, CpInfo::MethodRef(_) | CpInfo::InterfaceMethodRef(_)),
+            MethodHandleReferenceKind::InvokeInterface => matches!(target, CpInfo::InterfaceMethodRef(_)),
+        };
+        if !target_allowed {
+            return None;
+        }
+
+        let reference = self.resolve_ref(cp_method_handle.reference_index)?;
+
+        return Some(ResolvedMethodHandle { reference_kind: cp_method_handle.reference_kind, reference });
+    }
+}
+
+impl AttributeCode {
+    /**
+     * Ime razreda izjeme, ki jo handler lovi, oz. None, če handler lovi vse izjeme (catch_type je 0, npr. finally).
+     * Napaka pomeni, da catch_type ni 0 in ne kaže na CpClass.
+     */
+    pub fn catch_type_name<'a>(&self, entry: &ExceptionTableEntry, class_file: &'a ClassFile) -> Result<Option<&'a str>, InvalidConstantPoolReference> {
+        if entry.catch_type == 0 {
+            return Ok(None);
+        }
+
+        return match class_file.get_class_name(entry.catch_type) {
+            Some(class_name) => Ok(Some(class_name)),
+            None => Err(InvalidConstantPoolReference { msg: format!("catch_type {} handlerja na odmiku {} ne kaže na CpClass", entry.catch_type, entry.handler_pc) }),
+        };
+    }
+}
+
+impl AttributeModule {
+    /** Imena vmesnikov storitev iz izjav `uses`. Vrne None, če kateri od indeksov ne kaže na CpClass. */
+    pub fn uses_names<'a>(&self, class_file: &'a ClassFile) -> Option<Vec<&'a str>> {
+        return self.uses_index.iter().map(|&index| class_file.get_class_name(index)).collect();
+    }
+
+    /** Razrešene izjave `provides`. Vrne None, če kateri od indeksov ne kaže na CpClass. */
+    pub fn provides_names<'a>(&self, class_file: &'a ClassFile) -> Option<Vec<ModuleProvides<'a>>> {
+        let mut provides = Vec::with_capacity(self.provides.len());
+        for entry in &self.provides {
+            provides.push(ModuleProvides {
+                service: class_file.get_class_name(entry.provides_index)?,
+                implementations: entry.provides_with_index.iter().map(|&index| class_file.get_class_name(index)).collect::<Option<_>>()?,
+            });
+        }
+
+        return Some(provides);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec::Vec};
+
+    use crate::{fixture::{u16s, ClassBuilder}, parser::CpInfo};
+
+    #[test]
+    fn second_slot_of_long_and_double_is_not_a_constant() {
+        let mut builder = ClassBuilder::new("A");
+        let long_index = builder.long(7);
+        let double_index = builder.double(0.5);
+        let integer_index = builder.integer(3);
+        let class_file = builder.parse();
+
+        assert!(matches!(class_file.get_constant(long_index), Some(CpInfo::Long(_))));
+        assert!(class_file.get_constant(long_index + 1).is_none());
+        assert!(matches!(class_file.get_constant(double_index), Some(CpInfo::Double(_))));
+        assert!(class_file.get_constant(double_index + 1).is_none());
+        assert!(matches!(class_file.get_constant(integer_index), Some(CpInfo::Integer(_))));
+        assert!(class_file.get_constant(0).is_none());
+        assert!(class_file.get_constant(integer_index + 1).is_none());
+    }
+
+    #[test]
+    fn resolve_ref_rejects_placeholder_slot() {
+        let mut builder = ClassBuilder::new("A");
+        let field_ref = builder.field_ref("p/B", "x", "I");
+        let long_index = builder.long(1);
+        let class_file = builder.parse();
+
+        let member_ref = class_file.resolve_ref(field_ref).unwrap();
+        assert_eq!(member_ref.to_string(), "p/B.x:I");
+        assert!(class_file.resolve_ref(long_index + 1).is_none());
+        assert!(class_file.resolve_ref(class_file.this_class).is_none());
+    }
+
+    #[test]
+    fn catch_type_name_per_handler() {
+        let mut builder = ClassBuilder::new("A");
+        let exception = builder.class("java/io/IOException");
+        let integer = builder.integer(0);
+        // nop, return, astore_0, return
+        let code = builder.code(1, 1, &[0x00, 0xb1, 0x4b, 0xb1], &[[0, 2, 2, exception], [0, 2, 2, 0], [0, 2, 2, integer]], &[]);
+        builder.method(0x0009, "m", "()V", &[code]);
+        let class_file = builder.parse();
+        let code = class_file.methods[0].code().unwrap();
+
+        let names: Vec<_> = code.exception_table.iter().map(|entry| code.catch_type_name(entry, &class_file).map_err(|err| err.to_string())).collect();
+
+        assert_eq!(names, [
+            Ok(Some("java/io/IOException")),
+            Ok(None),
+            Err(format!("Neveljavna referenca v constant pool: catch_type {} handlerja na odmiku 2 ne kaže na CpClass.", integer)),
+        ]);
+    }
+
+    #[test]
+    fn resolve_method_handle_checks_kind_against_target() {
+        let mut builder = ClassBuilder::new("p/A");
+        let method_ref = builder.method_ref("p/A", "run", "()V");
+        let interface_class = builder.class("p/I");
+        let interface_name_and_type = builder.name_and_type("call", "()I");
+        let interface_method_ref = builder.entry(11, &u16s(&[interface_class, interface_name_and_type]));
+        let field_ref = builder.field_ref("p/A", "x", "I");
+        let mut method_handle = |kind: u8, reference_index: u16| builder.entry(15, &[&[kind][..], &reference_index.to_be_bytes()].concat());
+        let invoke_static = method_handle(6, method_ref);
+        let invoke_interface = method_handle(9, interface_method_ref);
+        let get_field = method_handle(1, field_ref);
+        let invoke_interface_on_method_ref = method_handle(9, method_ref);
+        let get_field_on_method_ref = method_handle(1, method_ref);
+        let invoke_virtual_on_interface_method_ref = method_handle(5, interface_method_ref);
+        let class_file = builder.parse();
+
+        let resolved = |index| class_file.resolve_method_handle(index).map(|method_handle| method_handle.to_string());
+
+        assert_eq!(resolved(invoke_static).as_deref(), Some("invokestatic p/A.run:()V"));
+        assert_eq!(resolved(invoke_interface).as_deref(), Some("invokeinterface p/I.call:()I"));
+        assert_eq!(resolved(get_field).as_deref(), Some("getfield p/A.x:I"));
+        assert_eq!(resolved(invoke_interface_on_method_ref), None);
+        assert_eq!(resolved(get_field_on_method_ref), None);
+        assert_eq!(resolved(invoke_virtual_on_interface_method_ref), None);
+        assert_eq!(resolved(method_ref), None);
+    }
+}
@@ -0,0 +1,352 @@
+use core::{error::Error, fmt::{self, Display, Formatter}};
+use alloc::{string::{String, ToString}, vec::Vec};
+
+use crate::{descriptor::FieldType, parser::{AttributeStackMapTable, ClassFile, MethodAccessFlagMask, StackMapAppendFrame, StackMapChopFrame, StackMapFrame, StackMapFullFrame, StackMapSameFrame, StackMapSameFrameExtended, StackMapSameLocals1StackItemFrame, StackMapSameLocals1StackItemFrameExtended, VerificationTypeDoubleVariableInfo, VerificationTypeFloatVariableInfo, VerificationTypeInfo, VerificationTypeIntegerVariableInfo, VerificationTypeLongVariableInfo, VerificationTypeUninitializedThisVariableInfo}};
+
+/** Največji offset_delta, ki ga lahko zapišeta SameFrame in SameLocals1StackItemFrame (frame_type 0-63 oz. 64-127) */
+const MAX_SHORT_OFFSET_DELTA: u16 = 63;
+
+/** Največje število lokalnih spremenljivk, ki jih lahko odstrani ChopFrame oz. doda AppendFrame */
+const MAX_CHOP_APPEND_LOCALS: usize = 3;
+
+#[derive(Debug, Clone)]
+pub struct InvalidStackMapFrame {
+    msg: String,
+}
+
+impl Display for InvalidStackMapFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Neveljaven okvir StackMapTable: {}.", self.msg)
+    }
+}
+
+impl Error for InvalidStackMapFrame {}
+
+/**
+ * Okvir s polnim stanjem lokalnih spremenljivk in sklada na absolutnem odmiku v kodi. Long in Double zasedata en element
+ * (kot v StackMapTable), čeprav v lokalnih spremenljivkah zasedata dve mesti.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbsoluteFrame {
+    pub offset: u16,
+    pub locals: Vec<VerificationTypeInfo>,
+    pub stack: Vec<VerificationTypeInfo>,
+}
+
+impl StackMapFrame {
+    pub fn offset_delta(&self) -> u16 {
+        match self {
+            StackMapFrame::SameFrame(frame) => frame.frame_type.into(),
+            StackMapFrame::SameLocals1StackItemFrame(frame) => (frame.frame_type - 64).into(),
+            StackMapFrame::SameLocals1StackItemFrameExtended(frame) => frame.offset_delta,
+            StackMapFrame::ChopFrame(frame) => frame.offset_delta,
+            StackMapFrame::SameFrameExtended(frame) => frame.offset_delta,
+            StackMapFrame::AppendFrame(frame) => frame.offset_delta,
+            StackMapFrame::FullFrame(frame) => frame.offset_delta,
+        }
+    }
+}
+
+impl AttributeStackMapTable {
+    /**
+     * Okvirji z absolutnimi odmiki in polnim stanjem. initial_locals so lokalne spremenljivke implicitnega začetnega okvirja
+     * (glej initial_frame_locals), glede na katerega je zapisan prvi okvir.
+     */
+    pub fn to_absolute_frames(&self, initial_locals: &[VerificationTypeInfo]) -> Result<Vec<AbsoluteFrame>, InvalidStackMapFrame> {
+        let mut frames = Vec::with_capacity(self.entries.len());
+        let mut locals = initial_locals.to_vec();
+        let mut previous_offset: Option<u16> = None;
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            // prvi okvir je na odmiku offset_delta, vsak naslednji pa na odmiku offset_delta + 1 za prejšnjim
+            let offset = match previous_offset {
+                None => Some(entry.offset_delta()),
+                Some(previous_offset) => previous_offset.checked_add(entry.offset_delta()).and_then(|offset| offset.checked_add(1)),
+            };
+            let Some(offset) = offset else {
+                return Err(InvalidStackMapFrame { msg: format!("odmik okvirja {} presega 65535", i) });
+            };
+
+            let stack = match entry {
+                StackMapFrame::SameFrame(_) | StackMapFrame::SameFrameExtended(_) => vec![],
+                StackMapFrame::SameLocals1StackItemFrame(StackMapSameLocals1StackItemFrame { stack_entry, .. })
+                | StackMapFrame::SameLocals1StackItemFrameExtended(StackMapSameLocals1StackItemFrameExtended { stack_entry, .. }) => vec![stack_entry.clone()],
+                StackMapFrame::ChopFrame(frame) => {
+                    let chopped = usize::from(251 - frame.frame_type);
+                    let Some(remaining) = locals.len().checked_sub(chopped) else {
+                        return Err(InvalidStackMapFrame { msg: format!("okvir {} odstrani {} lokalnih spremenljivk, obstaja pa jih {}", i, chopped, locals.len()) });
+                    };
+                    locals.truncate(remaining);
+                    vec![]
+                },
+                StackMapFrame::AppendFrame(frame) => {
+                    locals.extend(frame.locals.iter().cloned());
+                    vec![]
+                },
+                StackMapFrame::FullFrame(frame) => {
+                    locals = frame.locals.clone();
+                    frame.stack.clone()
+                },
+            };
+
+            frames.push(AbsoluteFrame { offset, locals: locals.clone(), stack });
+            previous_offset = Some(offset);
+        }
+
+        return Ok(frames);
+    }
+
+    /**
+     * Obratno od to_absolute_frames: okvirje zapiše z odmiki glede na prejšnji okvir in za vsakega izbere najkrajšo
+     * obliko (SameFrame, SameLocals1StackItemFrame, ChopFrame, AppendFrame, sicer FullFrame). Odmiki morajo strogo
+     * naraščati. attribute_length je izračunan iz zapisanih okvirjev.
+     */
+    pub fn from_absolute_frames(frames: &[AbsoluteFrame], initial_locals: &[VerificationTypeInfo]) -> Result<AttributeStackMapTable, InvalidStackMapFrame> {
+        let mut entries = Vec::with_capacity(frames.len());
+        let mut previous_locals = initial_locals;
+        let mut previous_offset: Option<u16> = None;
+
+        for frame in frames {
+            let offset_delta = match previous_offset {
+                None => frame.offset,
+                Some(previous_offset) if frame.offset > previous_offset => frame.offset - previous_offset - 1,
+                Some(previous_offset) => {
+                    return Err(InvalidStackMapFrame { msg: format!("odmik {} ni večji od odmika prejšnjega okvirja {}", frame.offset, previous_offset) });
+                },
+            };
+
+            entries.push(encode_frame(offset_delta, previous_locals, frame));
+            previous_locals = &frame.locals;
+            previous_offset = Some(frame.offset);
+        }
+
+        let attribute_length = 2 + entries.iter().map(frame_length).sum::<u32>();
+
+        return Ok(AttributeStackMapTable { attribute_length, entries, error: None });
+    }
+}
+
+fn encode_frame(offset_delta: u16, previous_locals: &[VerificationTypeInfo], frame: &AbsoluteFrame) -> StackMapFrame {
+    let same_locals = frame.locals == previous_locals;
+
+    if same_locals && frame.stack.is_empty() {
+        if offset_delta <= MAX_SHORT_OFFSET_DELTA {
+            return StackMapFrame::SameFrame(StackMapSameFrame { frame_type: offset_delta as u8 });
+        }
+        return StackMapFrame::SameFrameExtended(StackMapSameFrameExtended { frame_type: 251, offset_delta });
+    }
+
+    if same_locals && frame.stack.len() == 1 {
+        let stack_entry = frame.stack[0].clone();
+        if offset_delta <= MAX_SHORT_OFFSET_DELTA {
+            return StackMapFrame::SameLocals1StackItemFrame(StackMapSameLocals1StackItemFrame { frame_type: 64 + offset_delta as u8, stack_entry });
+        }
+        return StackMapFrame::SameLocals1StackItemFrameExtended(StackMapSameLocals1StackItemFrameExtended { frame_type: 247, offset_delta, stack_entry });
+    }
+
+    if frame.stack.is_empty() {
+        let chopped = previous_locals.len().saturating_sub(frame.locals.len());
+        if (1..=MAX_CHOP_APPEND_LOCALS).contains(&chopped) && previous_locals.starts_with(&frame.locals) {
+            return StackMapFrame::ChopFrame(StackMapChopFrame { frame_type: 251 - chopped as u8, offset_delta });
+        }
+
+        let appended = frame.locals.len().saturating_sub(previous_locals.len());
+        if (1..=MAX_CHOP_APPEND_LOCALS).contains(&appended) && frame.locals.starts_with(previous_locals) {
+            let locals = frame.locals[previous_locals.len()..].to_vec();
+            return StackMapFrame::AppendFrame(StackMapAppendFrame { frame_type: 251 + appended as u8, offset_delta, locals });
+        }
+    }
+
+    return StackMapFrame::FullFrame(StackMapFullFrame { frame_type: 255, offset_delta, locals: frame.locals.clone(), stack: frame.stack.clone() });
+}
+
+/** Dolžina zapisanega okvirja v bajtih */
+fn frame_length(frame: &StackMapFrame) -> u32 {
+    let types_length = |types: &[VerificationTypeInfo]| types.iter().map(verification_type_length).sum::<u32>();
+
+    match frame {
+        StackMapFrame::SameFrame(_) => 1,
+        StackMapFrame::SameLocals1StackItemFrame(frame) => 1 + verification_type_length(&frame.stack_entry),
+        StackMapFrame::SameLocals1StackItemFrameExtended(frame) => 3 + verification_type_length(&frame.stack_entry),
+        StackMapFrame::ChopFrame(_) | StackMapFrame::SameFrameExtended(_) => 3,
+        StackMapFrame::AppendFrame(frame) => 3 + types_length(&frame.locals),
+        StackMapFrame::FullFrame(frame) => 7 + types_length(&frame.locals) + types_length(&frame.stack),
+    }
+}
+
+fn verification_type_length(verification_type: &VerificationTypeInfo) -> u32 {
+    match verification_type {
+        VerificationTypeInfo::ObjectVariable(_) | VerificationTypeInfo::UninitializedVariable(_) => 3,
+        _ => 1,
+    }
+}
+
+impl ClassFile {
+    /**
+     * Lokalne spremenljivke implicitnega začetnega okvirja metode z indeksom method_index: `this` (v konstruktorju
+     * uninitializedThis) in parametri (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.10.1.6).
+     * Referenčni tipi parametrov kažejo na CpClass, ki ga prevajalnik pogosto ne zapiše, zato se manjkajoči CpClass
+     * vnosi dodajo v constant pool (glej intern_class).
+     */
+    pub fn initial_frame_locals(&mut self, method_index: usize) -> Result<Vec<VerificationTypeInfo>, InvalidStackMapFrame> {
+        let Some(method) = self.methods.get(method_index) else {
+            return Err(InvalidStackMapFrame { msg: format!("metoda {} ne obstaja", method_index) });
+        };
+        let Some(descriptor) = method.descriptor_parsed(self) else {
+            return Err(InvalidStackMapFrame { msg: format!("deskriptorja metode {} ni mogoče razčleniti", method_index) });
+        };
+
+        let mut locals = Vec::new();
+        if method.access_flags & MethodAccessFlagMask::Static as u16 == 0 {
+            let is_constructor = self.get_utf8(method.name_index) == Some("<init>");
+            if is_constructor && self.this_class_name() != Some("java/lang/Object") {
+                locals.push(VerificationTypeInfo::UninitializedThisVariable(VerificationTypeUninitializedThisVariableInfo {}));
+            }
+            else {
+                locals.push(VerificationTypeInfo::object(self.this_class));
+            }
+        }
+
+        for parameter in descriptor.parameters {
+            let verification_type = match parameter {
+                FieldType::Boolean | FieldType::Byte | FieldType::Char | FieldType::Short | FieldType::Int => VerificationTypeInfo::IntegerVariable(VerificationTypeIntegerVariableInfo {}),
+                FieldType::Float => VerificationTypeInfo::FloatVariable(VerificationTypeFloatVariableInfo {}),
+                FieldType::Long => VerificationTypeInfo::LongVariable(VerificationTypeLongVariableInfo {}),
+                FieldType::Double => VerificationTypeInfo::DoubleVariable(VerificationTypeDoubleVariableInfo {}),
+                FieldType::Object(class_name) => VerificationTypeInfo::object(self.intern_parameter_class(&class_name)?),
+                array_type @ FieldType::Array(_) => VerificationTypeInfo::object(self.intern_parameter_class(&field_type_descriptor(&array_type))?),
+            };
+            locals.push(verification_type);
+        }
+
+        return Ok(locals);
+    }
+
+    fn intern_parameter_class(&mut self, class_name: &str) -> Result<u16, InvalidStackMapFrame> {
+        return self.intern_class(class_name).map_err(|err| InvalidStackMapFrame { msg: err.to_string() });
+    }
+}
+
+/** Deskriptor tipa, npr. `[Ljava/lang/String;`. CpClass za tabele vsebuje deskriptor namesto imena razreda. */
+fn field_type_descriptor(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Byte => "B".into(),
+        FieldType::Char => "C".into(),
+        FieldType::Double => "D".into(),
+        FieldType::Float => "F".into(),
+        FieldType::Int => "I".into(),
+        FieldType::Long => "J".into(),
+        FieldType::Short => "S".into(),
+        FieldType::Boolean => "Z".into(),
+        FieldType::Object(class_name) => format!("L{};", class_name),
+        FieldType::Array(component_type) => format!("[{}", field_type_descriptor(component_type)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{fixture::{u16s, ClassBuilder}, parser::{AttributeInfo, VerificationTypeFloatVariableInfo}};
+
+    use super::*;
+
+    /**
+     * Statična metoda (Ljava/lang/String;J[I)V, katere StackMapTable vsebuje vse vrste okvirjev. CpClass za java/lang/String
+     * in [I ne obstajata, kot v classih, ki jih zapiše javac, kadar koda parametrov ne uporablja.
+     */
+    fn sample_class() -> ClassBuilder {
+        let mut builder = ClassBuilder::new("A");
+        let this_class = builder.this_class;
+        let [this_high, this_low] = this_class.to_be_bytes();
+        let frames: Vec<u8> = [
+            &u16s(&[7])[..],
+            &[3],
+            &[64 + 10, 1],
+            &[253, 0, 5, 1, 2],
+            &[250, 0, 2],
+            &[251, 0, 100],
+            &[247, 0, 70, 7, this_high, this_low],
+            &[255, 0, 0, 0, 1, 1, 0, 0],
+        ].concat();
+        let stack_map_table = builder.attribute("StackMapTable", &frames);
+        let mut code = vec![0x00; 199];
+        code.push(0xb1);
+        let code = builder.code(1, 6, &code, &[], &[stack_map_table]);
+        builder.method(0x0009, "m", "(Ljava/lang/String;J[I)V", &[code]);
+        builder.method(0x0001, "<init>", "(LA;)V", &[]);
+
+        return builder;
+    }
+
+    fn stack_map_table(class_file: &ClassFile) -> &AttributeStackMapTable {
+        let code = class_file.methods[0].code().unwrap();
+        let Some(AttributeInfo::StackMapTable(attribute_stack_map_table)) = code.attributes.first() else {
+            panic!("pričakovan StackMapTable");
+        };
+
+        return attribute_stack_map_table;
+    }
+
+    #[test]
+    fn initial_frame_locals_interns_missing_classes() {
+        let mut class_file = sample_class().parse();
+        let constant_pool_count = class_file.constant_pool.len();
+
+        let locals = class_file.initial_frame_locals(0).unwrap();
+
+        assert_eq!(class_file.constant_pool.len(), constant_pool_count + 4);
+        let VerificationTypeInfo::ObjectVariable(string) = &locals[0] else {
+            panic!("pričakovan ObjectVariable");
+        };
+        let VerificationTypeInfo::ObjectVariable(array) = &locals[2] else {
+            panic!("pričakovan ObjectVariable");
+        };
+        assert_eq!(class_file.get_class_name(string.cpool_index), Some("java/lang/String"));
+        assert_eq!(locals[1], VerificationTypeInfo::LongVariable(VerificationTypeLongVariableInfo {}));
+        assert_eq!(class_file.get_class_name(array.cpool_index), Some("[I"));
+
+        assert_eq!(class_file.initial_frame_locals(0).unwrap(), locals);
+        assert_eq!(class_file.constant_pool.len(), constant_pool_count + 4);
+    }
+
+    #[test]
+    fn initial_frame_locals_of_constructor() {
+        let mut class_file = sample_class().parse();
+        let this_class = class_file.this_class;
+
+        let locals = class_file.initial_frame_locals(1).unwrap();
+
+        assert_eq!(locals, [VerificationTypeInfo::UninitializedThisVariable(VerificationTypeUninitializedThisVariableInfo {}), VerificationTypeInfo::object(this_class)]);
+        assert!(class_file.initial_frame_locals(2).is_err());
+    }
+
+    #[test]
+    fn absolute_frames_round_trip() {
+        let mut class_file = sample_class().parse();
+        let initial_locals = class_file.initial_frame_locals(0).unwrap();
+        let stack_map_table = stack_map_table(&class_file);
+
+        let frames = stack_map_table.to_absolute_frames(&initial_locals).unwrap();
+
+        assert_eq!(frames.iter().map(|frame| frame.offset).collect::<Vec<_>>(), [3, 14, 20, 23, 124, 195, 196]);
+        let integer = VerificationTypeInfo::IntegerVariable(VerificationTypeIntegerVariableInfo {});
+        let float = VerificationTypeInfo::FloatVariable(VerificationTypeFloatVariableInfo {});
+        assert_eq!(frames[2].locals, [&initial_locals[..], &[integer.clone(), float]].concat());
+        assert_eq!(frames[3].locals, [&initial_locals[..], core::slice::from_ref(&integer)].concat());
+        assert_eq!(frames[6].locals, [integer]);
+        assert_eq!(&AttributeStackMapTable::from_absolute_frames(&frames, &initial_locals).unwrap(), stack_map_table);
+    }
+
+    #[test]
+    fn offsets_must_increase() {
+        let frame = AbsoluteFrame { offset: 5, locals: vec![], stack: vec![] };
+
+        assert!(AttributeStackMapTable::from_absolute_frames(&[frame.clone(), frame], &[]).is_err());
+    }
+
+    #[test]
+    fn chop_frame_cannot_remove_missing_locals() {
+        let attribute_stack_map_table = AttributeStackMapTable { attribute_length: 5, entries: vec![StackMapFrame::ChopFrame(StackMapChopFrame { frame_type: 250, offset_delta: 0 })], error: None };
+
+        assert!(attribute_stack_map_table.to_absolute_frames(&[]).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use alloc::vec::Vec;
+
+use crate::{disassembler::disassemble, parser::ClassFile};
+
+/** Velikost in zahtevnost ene metode. Metode brez Code atributa imajo vse podatke o kodi None. */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MethodStats<'a> {
+    /** None, če name_index ne kaže na CpUtf8 */
+    pub name: Option<&'a str>,
+    /** None, če descriptor_index ne kaže na CpUtf8 */
+    pub descriptor: Option<&'a str>,
+    /** Dolžina bytecoda v bajtih */
+    pub code_length: Option<u32>,
+    /** None tudi, če bytecoda ni mogoče razčleniti */
+    pub instruction_count: Option<usize>,
+    pub max_stack: Option<u16>,
+    pub max_locals: Option<u16>,
+}
+
+impl ClassFile {
+    /** Statistika za vse metode v vrstnem redu, v katerem so zapisane v classu. */
+    pub fn method_stats(&self) -> Vec<MethodStats<'_>> {
+        return self.methods.iter().map(|method| {
+            let code = method.code();
+
+            return MethodStats {
+                name: self.get_utf8(method.name_index),
+                descriptor: self.get_utf8(method.descriptor_index),
+                code_length: code.map(|code| code.code.len() as u32),
+                instruction_count: code.and_then(|code| disassemble(&code.code).ok()).map(|instructions| instructions.len()),
+                max_stack: code.map(|code| code.max_stack),
+                max_locals: code.map(|code| code.max_locals),
+            };
+        }).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::ClassBuilder;
+
+    use super::*;
+
+    #[test]
+    fn method_stats_of_hand_disassembled_methods() {
+        let mut builder = ClassBuilder::new("A");
+        let [field_high, field_low] = builder.field_ref("A", "x", "I").to_be_bytes();
+        let [method_high, method_low] = builder.method_ref("A", "f", "()V").to_be_bytes();
+        let code = builder.code(2, 1, &[
+            // 0: iload_0
+            0x1a,
+            // 1: tableswitch z dvema bajtoma poravnave, default in edini primer skočita na return
+            0xaa, 0x00, 0x00, 0, 0, 0, 25, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 25,
+            // 20: getstatic A.x:I
+            0xb2, field_high, field_low,
+            // 23: invokestatic A.f:()V
+            0xb8, method_high, method_low,
+            // 26: return
+            0xb1,
+        ], &[], &[]);
+        builder.method(0x0009, "s", "(I)V", &[code]);
+        builder.method(0x0401, "a", "()V", &[]);
+        // ifeq brez drugega bajta odmika
+        let malformed = builder.code(1, 0, &[0x99, 0x00], &[], &[]);
+        builder.method(0x0009, "m", "()V", &[malformed]);
+        let mut class_file = builder.parse();
+        class_file.methods[1].name_index = class_file.this_class;
+
+        assert_eq!(class_file.method_stats(), [
+            MethodStats { name: Some("s"), descriptor: Some("(I)V"), code_length: Some(27), instruction_count: Some(5), max_stack: Some(2), max_locals: Some(1) },
+            MethodStats { name: None, descriptor: Some("()V"), code_length: None, instruction_count: None, max_stack: None, max_locals: None },
+            MethodStats { name: Some("m"), descriptor: Some("()V"), code_length: Some(2), instruction_count: None, max_stack: Some(1), max_locals: Some(0) },
+        ]);
+    }
+}
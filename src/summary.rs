@@ -0,0 +1,75 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{inspect::ClassVersion, parser::ClassFile};
+
+/**
+ * Povzetek classa brez constant poola in bytecoda, npr. za seznam classov v JAR datoteki. Imena so v internal form
+ * (`java/lang/Object`).
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassSummary {
+    /** None, če this_class ne kaže na CpClass z imenom */
+    pub name: Option<String>,
+    /** None pri java/lang/Object, module-info in če super_class ni mogoče razrešiti */
+    pub super_class: Option<String>,
+    /** Vmesniki, ki jih ni mogoče razrešiti, so izpuščeni */
+    pub interfaces: Vec<String>,
+    pub version: ClassVersion,
+    pub access_flags: u16,
+    pub field_count: usize,
+    pub method_count: usize,
+}
+
+impl From<&ClassFile> for ClassSummary {
+    fn from(class_file: &ClassFile) -> ClassSummary {
+        return ClassSummary {
+            name: class_file.this_class_name().map(String::from),
+            super_class: class_file.super_class_name().map(String::from),
+            interfaces: class_file.interfaces.iter().filter_map(|&interface| class_file.get_class_name(interface)).map(String::from).collect(),
+            version: class_file.version(),
+            access_flags: class_file.access_flags,
+            field_count: class_file.fields.len(),
+            method_count: class_file.methods.len(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture::ClassBuilder;
+
+    use super::*;
+
+    #[test]
+    fn summary_from_class_file() {
+        let mut builder = ClassBuilder::new("p/A");
+        let runnable = builder.class("java/lang/Runnable");
+        builder.interfaces = vec![runnable, 0x7FFF];
+        builder.field(0x0002, "x", "I", &[]);
+        builder.method(0x0001, "run", "()V", &[]);
+        builder.method(0x0001, "stop", "()V", &[]);
+
+        let summary = ClassSummary::from(&builder.parse());
+
+        assert_eq!(summary, ClassSummary {
+            name: Some("p/A".into()),
+            super_class: Some("java/lang/Object".into()),
+            interfaces: vec!["java/lang/Runnable".into()],
+            version: ClassVersion { major: 52, minor: 0 },
+            access_flags: 0x0021,
+            field_count: 1,
+            method_count: 2,
+        });
+    }
+
+    #[test]
+    fn summary_without_super_class() {
+        let mut builder = ClassBuilder::new("java/lang/Object");
+        builder.super_class = 0;
+
+        let summary = ClassSummary::from(&builder.parse());
+
+        assert_eq!(summary.name.as_deref(), Some("java/lang/Object"));
+        assert_eq!(summary.super_class, None);
+    }
+}
@@ -0,0 +1,452 @@
+use core::{error::Error, fmt::{self, Display, Formatter}, iter::Peekable, str::Chars};
+use alloc::{collections::{BTreeMap, BTreeSet}, string::String};
+
+use crate::{parser::{AttributeAnnotationsElementValue, AttributeInfo, AttributeRuntimeAnnotationsEntry, ClassFile, CpClass, CpInfo, CpUtf8}, util::string_to_modified_utf8};
+
+/** Največje število vnosov constant poola (vključno z "dummy" vnosom na indeksu 0), saj je constant_pool_count u16. */
+const MAX_CONSTANT_POOL_ENTRIES: usize = u16::MAX as usize;
+
+#[derive(Debug, Clone)]
+pub struct TransformError {
+    msg: String,
+}
+
+impl Display for TransformError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Napaka pri preoblikovanju classa: {}.", self.msg)
+    }
+}
+
+impl Error for TransformError {}
+
+impl ClassFile {
+    /** Vrne indeks CpUtf8 z dano vrednostjo. Če tak vnos še ne obstaja, ga doda na konec constant poola. */
+    pub fn intern_utf8(&mut self, value: &str) -> Result<u16, TransformError> {
+        let existing_index = self.constant_pool.iter().enumerate().skip(1).find_map(|(index, entry)| match entry {
+            CpInfo::Utf8(cp_utf8) if cp_utf8.converted == value => Some(index),
+            _ => None,
+        });
+        if let Some(index) = existing_index {
+            return Ok(index as u16);
+        }
+
+        if self.constant_pool.len() >= MAX_CONSTANT_POOL_ENTRIES {
+            return Err(TransformError { msg: format!("constant pool je poln, ni mogoče dodati \"{}\"", value) });
+        }
+
+        self.constant_pool.push(CpInfo::Utf8(CpUtf8 { bytes: string_to_modified_utf8(value), converted: value.into() }));
+        self.constant_pool_count = self.constant_pool.len() as u16;
+
+        return Ok(self.constant_pool_count - 1);
+    }
+
+    /** Vrne indeks CpClass z danim imenom (internal form). Če tak vnos še ne obstaja, ga (in po potrebi CpUtf8) doda na konec. */
+    pub fn intern_class(&mut self, internal_name: &str) -> Result<u16, TransformError> {
+        let existing_index = (1..self.constant_pool.len()).find(|&index| {
+            matches!(self.constant_pool[index], CpInfo::Class(_)) && self.get_class_name(index as u16) == Some(internal_name)
+        });
+        if let Some(index) = existing_index {
+            return Ok(index as u16);
+        }
+
+        let name_index = self.intern_utf8(internal_name)?;
+        if self.constant_pool.len() >= MAX_CONSTANT_POOL_ENTRIES {
+            return Err(TransformError { msg: format!("constant pool je poln, ni mogoče dodati razreda {}", internal_name) });
+        }
+
+        self.constant_pool.push(CpInfo::Class(CpClass { name_index }));
+        self.constant_pool_count = self.constant_pool.len() as u16;
+
+        return Ok(self.constant_pool_count - 1);
+    }
+
+    /**
+     * Preimenuje razred (new_internal_name je v internal form, npr. `com/example/Foo`). Spremeni se le CpClass, na katerega
+     * kaže this_class, zato se preimenujejo vse reference nanj. CpUtf8 s starim imenom se ne spreminja, ker si ga lahko delijo
+     * tudi drugi vnosi (drug CpClass, deskriptor, string konstanta, ime atributa), temveč CpClass dobi nov CpUtf8. Stari vnos
+     * ostane v constant poolu, tudi če ni več uporabljen. Deskriptorji in signature se ne spremenijo, za to je remap_classes.
+     */
+    pub fn rename(&mut self, new_internal_name: &str) -> Result<(), TransformError> {
+        let name_index = self.intern_utf8(new_internal_name)?;
+        match self.constant_pool.get_mut(usize::from(self.this_class)) {
+            Some(CpInfo::Class(cp_class)) if self.this_class != 0 => cp_class.name_index = name_index,
+            _ => return Err(TransformError { msg: format!("this_class {} ne kaže na CpClass", self.this_class) }),
+        };
+
+        return Ok(());
+    }
+
+    /**
+     * Preslika vsa imena razredov: imena v CpClass (tudi tabele, npr. `[Lfoo/Bar;`) ter razrede v deskriptorjih in signaturah
+     * (polja, metode, CpNameAndType, CpMethodType, Signature, LocalVariableTable, LocalVariableTypeTable, anotacije, komponente
+     * recorda). mapping dobi ime v internal form in vrne None, če se ime ne spremeni. Kot pri rename se CpUtf8 ne spreminjajo,
+     * temveč se reference preusmerijo na nove vnose, zato se npr. string konstante z enako vsebino ne spremenijo.
+     * Neveljavni deskriptorji in signature ostanejo nespremenjeni.
+     */
+    pub fn remap_classes(&mut self, mapping: impl Fn(&str) -> Option<String>) -> Result<(), TransformError> {
+        let class_name_indices: BTreeSet<u16> = self.constant_pool.iter().filter_map(|entry| match entry {
+            CpInfo::Class(cp_class) => Some(cp_class.name_index),
+            _ => None,
+        }).collect();
+        let mut descriptor_indices = BTreeSet::new();
+        self.visit_descriptor_indices(&mut |index| { descriptor_indices.insert(*index); });
+
+        let mut remapped_class_names = BTreeMap::new();
+        for index in class_name_indices {
+            let remapped = match self.get_utf8(index) {
+                Some(name) if name.starts_with('[') => remap_signature(name, &mapping),
+                Some(name) => mapping(name).filter(|remapped| remapped != name),
+                None => None,
+            };
+            if let Some(remapped) = remapped {
+                remapped_class_names.insert(index, self.intern_utf8(&remapped)?);
+            }
+        }
+
+        let mut remapped_descriptors = BTreeMap::new();
+        for index in descriptor_indices {
+            if let Some(remapped) = self.get_utf8(index).and_then(|descriptor| remap_signature(descriptor, &mapping)) {
+                remapped_descriptors.insert(index, self.intern_utf8(&remapped)?);
+            }
+        }
+
+        for entry in self.constant_pool.iter_mut() {
+            if let CpInfo::Class(cp_class) = entry && let Some(&name_index) = remapped_class_names.get(&cp_class.name_index) {
+                cp_class.name_index = name_index;
+            }
+        }
+        self.visit_descriptor_indices(&mut |index| {
+            if let Some(&descriptor_index) = remapped_descriptors.get(index) {
+                *index = descriptor_index;
+            }
+        });
+
+        return Ok(());
+    }
+
+    /** Obišče vse indekse CpUtf8, ki vsebujejo deskriptor ali signaturo. */
+    fn visit_descriptor_indices(&mut self, visit: &mut impl FnMut(&mut u16)) {
+        for entry in self.constant_pool.iter_mut() {
+            match entry {
+                CpInfo::NameAndType(cp_name_and_type) => visit(&mut cp_name_and_type.descriptor_index),
+                CpInfo::MethodType(cp_method_type) => visit(&mut cp_method_type.descriptor_index),
+                _ => (),
+            }
+        }
+        for field in self.fields.iter_mut() {
+            visit(&mut field.descriptor_index);
+            visit_attributes_descriptor_indices(&mut field.attributes, visit);
+        }
+        for method in self.methods.iter_mut() {
+            visit(&mut method.descriptor_index);
+            visit_attributes_descriptor_indices(&mut method.attributes, visit);
+        }
+        visit_attributes_descriptor_indices(&mut self.attributes, visit);
+    }
+}
+
+fn visit_attributes_descriptor_indices(attributes: &mut [AttributeInfo], visit: &mut impl FnMut(&mut u16)) {
+    for attribute in attributes.iter_mut() {
+        match attribute {
+            AttributeInfo::Code(attribute_code) => visit_attributes_descriptor_indices(&mut attribute_code.attributes, visit),
+            AttributeInfo::Signature(attribute_signature) => visit(&mut attribute_signature.signature_index),
+            AttributeInfo::LocalVariableTable(attribute_local_variable_table) => {
+                for entry in attribute_local_variable_table.local_variable_table.iter_mut() {
+                    visit(&mut entry.descriptor_index);
+                }
+            },
+            AttributeInfo::LocalVariableTypeTable(attribute_local_variable_type_table) => {
+                for entry in attribute_local_variable_type_table.local_variable_table.iter_mut() {
+                    visit(&mut entry.signature_index);
+                }
+            },
+            AttributeInfo::RuntimeVisibleAnnotations(attribute_annotations) => visit_annotations_descriptor_indices(&mut attribute_annotations.annotations, visit),
+            AttributeInfo::RuntimeInvisibleAnnotations(attribute_annotations) => visit_annotations_descriptor_indices(&mut attribute_annotations.annotations, visit),
+            AttributeInfo::RuntimeVisibleParameterAnnotations(attribute_parameter_annotations) => {
+                for annotations in attribute_parameter_annotations.parameter_annotations.iter_mut() {
+                    visit_annotations_descriptor_indices(annotations, visit);
+                }
+            },
+            AttributeInfo::RuntimeInvisibleParameterAnnotations(attribute_parameter_annotations) => {
+                for annotations in attribute_parameter_annotations.parameter_annotations.iter_mut() {
+                    visit_annotations_descriptor_indices(annotations, visit);
+                }
+            },
+            AttributeInfo::RuntimeVisibleTypeAnnotations(attribute_type_annotations) => {
+                for annotation in attribute_type_annotations.annotations.iter_mut() {
+                    visit(&mut annotation.type_index);
+                    for pair in annotation.element_value_pairs.iter_mut() {
+                        visit_element_value_descriptor_indices(&mut pair.element_value, visit);
+                    }
+                }
+            },
+            AttributeInfo::RuntimeInvisibleTypeAnnotations(attribute_type_annotations) => {
+                for annotation in attribute_type_annotations.annotations.iter_mut() {
+                    visit(&mut annotation.type_index);
+                    for pair in annotation.element_value_pairs.iter_mut() {
+                        visit_element_value_descriptor_indices(&mut pair.element_value, visit);
+                    }
+                }
+            },
+            AttributeInfo::AnnotationDefault(attribute_annotation_default) => visit_element_value_descriptor_indices(&mut attribute_annotation_default.default_value, visit),
+            AttributeInfo::Record(attribute_record) => {
+                for component in attribute_record.components.iter_mut() {
+                    visit(&mut component.descriptor_index);
+                    visit_attributes_descriptor_indices(&mut component.attributes, visit);
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+fn visit_annotations_descriptor_indices(annotations: &mut [AttributeRuntimeAnnotationsEntry], visit: &mut impl FnMut(&mut u16)) {
+    for annotation in annotations.iter_mut() {
+        visit(&mut annotation.type_index);
+        for pair in annotation.element_value_pairs.iter_mut() {
+            visit_element_value_descriptor_indices(&mut pair.element_value, visit);
+        }
+    }
+}
+
+fn visit_element_value_descriptor_indices(element_value: &mut AttributeAnnotationsElementValue, visit: &mut impl FnMut(&mut u16)) {
+    match element_value {
+        AttributeAnnotationsElementValue::EnumClass(enum_class) => visit(&mut enum_class.type_name_index),
+        AttributeAnnotationsElementValue::Class(class) => visit(&mut class.class_info_index),
+        AttributeAnnotationsElementValue::AnnotationInterface(annotation_interface) => {
+            visit_annotations_descriptor_indices(core::slice::from_mut(&mut annotation_interface.annotation_value), visit);
+        },
+        AttributeAnnotationsElementValue::ArrayType(array_type) => {
+            for value in array_type.values.iter_mut() {
+                visit_element_value_descriptor_indices(value, visit);
+            }
+        },
+        _ => (),
+    }
+}
+
+/**
+ * Preslika imena razredov v deskriptorju ali signaturi (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.7.9.1).
+ * Vrne None, če se nič ne spremeni ali signatura ni veljavna.
+ */
+fn remap_signature(signature: &str, mapping: &impl Fn(&str) -> Option<String>) -> Option<String> {
+    let mut remapped = String::with_capacity(signature.len());
+    let mut chars = signature.chars().peekable();
+
+    // formalni parametri tipa, npr. <T:Ljava/lang/Object;U::Ljava/lang/Comparable<TT;>;>
+    if chars.peek() == Some(&'<') {
+        remapped.push(chars.next()?);
+        while *chars.peek()? != '>' {
+            loop {
+                let c = chars.next()?;
+                remapped.push(c);
+                if c == ':' {
+                    break;
+                }
+            }
+            if !matches!(chars.peek()?, ':' | '>') {
+                remap_type_signature(&mut chars, &mut remapped, mapping)?;
+            }
+            while chars.peek() == Some(&':') {
+                remapped.push(chars.next()?);
+                remap_type_signature(&mut chars, &mut remapped, mapping)?;
+            }
+        }
+        remapped.push(chars.next()?);
+    }
+
+    while let Some(&c) = chars.peek() {
+        if matches!(c, '(' | ')' | '^') {
+            remapped.push(c);
+            chars.next();
+        }
+        else {
+            remap_type_signature(&mut chars, &mut remapped, mapping)?;
+        }
+    }
+
+    if remapped == signature {
+        return None;
+    }
+
+    return Some(remapped);
+}
+
+fn remap_type_signature(chars: &mut Peekable<Chars>, remapped: &mut String, mapping: &impl Fn(&str) -> Option<String>) -> Option<()> {
+    let c = chars.next()?;
+    remapped.push(c);
+    match c {
+        'B' | 'C' | 'D' | 'F' | 'I' | 'J' | 'S' | 'Z' | 'V' => (),
+        '[' => remap_type_signature(chars, remapped, mapping)?,
+        // spremenljivka tipa, npr. TT;
+        'T' => loop {
+            let c = chars.next()?;
+            remapped.push(c);
+            if c == ';' {
+                break;
+            }
+        },
+        'L' => {
+            let mut class_name = String::new();
+            while !matches!(chars.peek()?, ';' | '<' | '.') {
+                class_name.push(chars.next()?);
+            }
+            match mapping(&class_name) {
+                Some(remapped_class_name) => remapped.push_str(&remapped_class_name),
+                None => remapped.push_str(&class_name),
+            };
+
+            loop {
+                let c = chars.next()?;
+                remapped.push(c);
+                match c {
+                    ';' => break,
+                    '<' => {
+                        while *chars.peek()? != '>' {
+                            match chars.peek()? {
+                                '*' => remapped.push(chars.next()?),
+                                '+' | '-' => {
+                                    remapped.push(chars.next()?);
+                                    remap_type_signature(chars, remapped, mapping)?;
+                                },
+                                _ => remap_type_signature(chars, remapped, mapping)?,
+                            }
+                        }
+                        remapped.push(chars.next()?);
+                    },
+                    // notranji razred parametriziranega razreda, npr. Lfoo/Outer<TT;>.Inner; (ime je enostavno, zato se ne preslika)
+                    '.' => {
+                        while !matches!(chars.peek()?, ';' | '<' | '.') {
+                            remapped.push(chars.next()?);
+                        }
+                    },
+                    _ => return None,
+                }
+            }
+        },
+        _ => return None,
+    }
+
+    return Some(());
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::ToString, vec::Vec};
+
+    use crate::{fixture::{u16s, ClassBuilder}, io::Cursor, parser::{parse_one, AttributeInfo, CpInfo}};
+
+    use super::*;
+
+    #[test]
+    fn rename_keeps_utf8_shared_with_method_descriptor() {
+        let mut builder = ClassBuilder::new("p/A");
+        // ClassBuilder deli enake CpUtf8, zato deskriptor metode kaže na isti vnos kot ime razreda
+        builder.method(0x0401, "m", "p/A", &[]);
+        let other_class = builder.class("p/A");
+        let mut class_file = builder.parse();
+        let shared_index = class_file.methods[0].descriptor_index;
+        assert_eq!(class_file.get_class_name(class_file.this_class), class_file.get_utf8(shared_index));
+
+        class_file.rename("q/B").unwrap();
+
+        assert_eq!(class_file.this_class_name(), Some("q/B"));
+        assert_eq!(class_file.methods[0].descriptor_index, shared_index);
+        assert_eq!(class_file.get_utf8(shared_index), Some("p/A"));
+        assert_eq!(class_file.get_class_name(other_class), Some("p/A"));
+        assert_eq!(class_file.super_class_name(), Some("java/lang/Object"));
+    }
+
+    #[test]
+    fn rename_keeps_descriptors_and_round_trips() {
+        let mut builder = ClassBuilder::new("p/A");
+        builder.field(0x0002, "next", "Lp/A;", &[]);
+        let string_index = builder.string("p/A");
+        let mut class_file = builder.parse();
+        let constant_pool_count = class_file.constant_pool_count;
+
+        class_file.rename("q/B").unwrap();
+
+        assert_eq!(class_file.this_class_name(), Some("q/B"));
+        assert_eq!(class_file.get_utf8(class_file.fields[0].descriptor_index), Some("Lp/A;"));
+        assert_eq!(class_file.get_string(string_index), Some("p/A"));
+        assert_eq!(class_file.constant_pool_count, constant_pool_count + 1);
+        let bytes = class_file.to_bytes().unwrap();
+        assert_eq!(parse_one(&mut Cursor::new(bytes)).unwrap(), class_file);
+    }
+
+    #[test]
+    fn rename_without_this_class_is_error() {
+        let mut builder = ClassBuilder::new("p/A");
+        builder.this_class = 0;
+        let mut class_file = builder.parse();
+
+        assert!(class_file.rename("q/B").is_err());
+    }
+
+    /** p/A s polji, metodo in konstantami, ki omenjajo p/A in p/B na vse načine, ki jih remap_classes obravnava. */
+    fn class_for_remapping() -> ClassBuilder {
+        let mut builder = ClassBuilder::new("p/A");
+        builder.field(0x0002, "next", "Lp/A;", &[]);
+        builder.field(0x0002, "broken", "Lp/A", &[]);
+        let signature = builder.utf8("Ljava/util/Map<Lp/A;Ljava/util/List<+Lp/B;>;>;");
+        let signature = builder.attribute("Signature", &u16s(&[signature]));
+        builder.field(0x0002, "map", "Ljava/util/Map;", &[signature]);
+        builder.method(0x0401, "convert", "(Lp/B;[[I)[Lp/A;", &[]);
+        let class_signature = builder.utf8("<T:Lp/B;>Ljava/lang/Object;");
+        let class_signature = builder.attribute("Signature", &u16s(&[class_signature]));
+        builder.class_attribute(class_signature);
+        builder.class("p/B");
+        builder.class("[Lp/A;");
+        builder.class("p/C");
+        builder.string("p/B");
+
+        return builder;
+    }
+
+    fn class_names(class_file: &ClassFile) -> Vec<&str> {
+        return (1..class_file.constant_pool.len() as u16).filter_map(|index| class_file.get_class_name(index)).collect();
+    }
+
+    #[test]
+    fn remap_classes_rewrites_class_entries_descriptors_and_signatures() {
+        let mut class_file = class_for_remapping().parse();
+
+        class_file.remap_classes(|name| match name {
+            "p/A" => Some("q/A".to_string()),
+            "p/B" => Some("q/B".to_string()),
+            _ => None,
+        }).unwrap();
+
+        assert_eq!(class_names(&class_file), ["q/A", "java/lang/Object", "q/B", "[Lq/A;", "p/C"]);
+        let field_descriptors: Vec<_> = class_file.fields.iter().map(|field| class_file.get_utf8(field.descriptor_index).unwrap()).collect();
+        assert_eq!(field_descriptors, ["Lq/A;", "Lp/A", "Ljava/util/Map;"]);
+        assert_eq!(class_file.get_utf8(class_file.methods[0].descriptor_index), Some("(Lq/B;[[I)[Lq/A;"));
+        let AttributeInfo::Signature(field_signature) = &class_file.fields[2].attributes[0] else {
+            panic!("pričakovan Signature");
+        };
+        assert_eq!(class_file.get_utf8(field_signature.signature_index), Some("Ljava/util/Map<Lq/A;Ljava/util/List<+Lq/B;>;>;"));
+        let AttributeInfo::Signature(class_signature) = &class_file.attributes[0] else {
+            panic!("pričakovan Signature");
+        };
+        assert_eq!(class_file.get_utf8(class_signature.signature_index), Some("<T:Lq/B;>Ljava/lang/Object;"));
+        let string_index = class_file.constant_pool.iter().position(|entry| matches!(entry, CpInfo::String(_))).unwrap() as u16;
+        assert_eq!(class_file.get_string(string_index), Some("p/B"));
+
+        let bytes = class_file.to_bytes().unwrap();
+        assert_eq!(parse_one(&mut Cursor::new(bytes)).unwrap(), class_file);
+    }
+
+    #[test]
+    fn remap_classes_without_changes_keeps_class_intact() {
+        let original = class_for_remapping().parse();
+        let mut class_file = class_for_remapping().parse();
+
+        class_file.remap_classes(|_| None).unwrap();
+        assert_eq!(class_file, original);
+
+        // preslikava v enako ime ni sprememba
+        class_file.remap_classes(|name| Some(name.to_string())).unwrap();
+        assert_eq!(class_file, original);
+    }
+}
@@ -1,4 +1,7 @@
-use std::{error::Error, fmt::{self, Display, Formatter}};
+use core::{error::Error, fmt::{self, Display, Formatter}};
+use alloc::{string::String, vec::Vec};
+
+use crate::io::{self, Read};
 
 #[derive(Debug, Clone)]
 pub struct MalformedModifiedUtf8 {
@@ -24,7 +27,7 @@ impl MalformedModifiedUtf8 {
 }
 
 /** https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.4.7 */
-pub fn modified_utf8_to_string(bytes: &Vec<u8>) -> Result<String, MalformedModifiedUtf8> {
+pub fn modified_utf8_to_string(bytes: &[u8]) -> Result<String, MalformedModifiedUtf8> {
     let mut converted: Vec<char> = vec![];
     let mut i: usize = 0;
     while i < bytes.len() {
@@ -75,3 +78,91 @@ pub fn modified_utf8_to_string(bytes: &Vec<u8>) -> Result<String, MalformedModif
 
     return Ok(String::from_iter(converted));
 }
+
+/** Obratno od modified_utf8_to_string. Znaki izven BMP se zapišejo kot par surogatov, vsak s 3 bajti. */
+pub fn string_to_modified_utf8(string: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(string.len());
+    for unit in string.encode_utf16() {
+        if unit != 0 && unit <= 0x7F {
+            bytes.push(unit as u8);
+        }
+        else if unit <= 0x7FF {
+            bytes.push(0b1100_0000 | (unit >> 6) as u8);
+            bytes.push(0b1000_0000 | (unit & 0x3F) as u8);
+        }
+        else {
+            bytes.push(0b1110_0000 | (unit >> 12) as u8);
+            bytes.push(0b1000_0000 | ((unit >> 6) & 0x3F) as u8);
+            bytes.push(0b1000_0000 | (unit & 0x3F) as u8);
+        }
+    }
+
+    return bytes;
+}
+
+/** Bralnik, ki šteje prebrane bajte. Uporaben za odmike v sporočilih o napakah in za preverjanje dolžin struktur. */
+#[derive(Debug)]
+pub struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R: Read> CountingReader<R> {
+    pub fn new(inner: R) -> CountingReader<R> {
+        return CountingReader { inner, count: 0 };
+    }
+
+    /** Število do zdaj prebranih bajtov. */
+    pub fn position(&self) -> u64 {
+        return self.count;
+    }
+
+    pub fn into_inner(self) -> R {
+        return self.inner;
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        self.count += bytes_read as u64;
+
+        return Ok(bytes_read);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counting_reader_counts_short_reads() {
+        let bytes: Vec<u8> = (1..=10).collect();
+        let mut reader = CountingReader::new(bytes.as_slice());
+        let mut buf = [0; 8];
+
+        assert_eq!(reader.read(&mut buf[..4]).unwrap(), 4);
+        assert_eq!(reader.position(), 4);
+        // ostalo je le 6 bajtov
+        assert_eq!(reader.read(&mut buf).unwrap(), 6);
+        assert_eq!(buf[..6], [5, 6, 7, 8, 9, 10]);
+        assert_eq!(reader.position(), 10);
+        assert_eq!(reader.read(&mut buf).unwrap(), 0);
+        assert_eq!(reader.position(), 10);
+        assert!(reader.into_inner().is_empty());
+    }
+
+    #[test]
+    fn counting_reader_counts_bytes_before_unexpected_end() {
+        let bytes = [1, 2, 3];
+        let mut reader = CountingReader::new(&bytes[..]);
+
+        assert!(reader.read_exact(&mut [0; 5]).is_err());
+        assert_eq!(reader.position(), 3);
+
+        let mut reader = CountingReader::new(&bytes[..]);
+        reader.read_exact(&mut [0; 2]).unwrap();
+        assert_eq!(reader.position(), 2);
+        assert_eq!(reader.into_inner(), [3]);
+    }
+}
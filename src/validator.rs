@@ -0,0 +1,653 @@
+use core::fmt::{self, Display, Formatter};
+use alloc::{string::String, vec::Vec};
+
+use crate::{descriptor::FieldType, disassembler::{disassemble, switch_padding_len, Instruction, MalformedBytecode, Opcode}, parser::{AttributeCode, AttributeEnclosingMethod, AttributeInfo, ClassFile, CpInfo, MethodInfo}};
+
+/** Prvi major_version (Java 6), pri katerem Code atributi vsebujejo StackMapTable */
+const STACK_MAP_TABLE_MIN_MAJOR_VERSION: u16 = 50;
+
+fn method_display_name(class_file: &ClassFile, method: &MethodInfo) -> String {
+    return format!("{}{}", class_file.get_utf8(method.name_index).unwrap_or("?"), class_file.get_utf8(method.descriptor_index).unwrap_or(""));
+}
+
+/** Code atribut metode in njegovi razčlenjeni ukazi oz. napaka, če bytecoda ni mogoče razčleniti. */
+struct MethodCode<'a> {
+    method: &'a MethodInfo,
+    code: &'a AttributeCode,
+    instructions: Result<Vec<Instruction>, MalformedBytecode>,
+}
+
+/** Razčleni kodo vseh metod. Validatorji bytecoda si rezultat delijo, da se vsaka metoda razčleni le enkrat. */
+fn disassemble_methods(class_file: &ClassFile) -> Vec<MethodCode<'_>> {
+    return class_file.methods.iter()
+        .filter_map(|method| {
+            let code = method.code()?;
+            return Some(MethodCode { method, code, instructions: disassemble(&code.code) });
+        })
+        .collect();
+}
+
+fn undecodable_code_problems(class_file: &ClassFile, method_codes: &[MethodCode]) -> Vec<String> {
+    return method_codes.iter()
+        .filter_map(|method_code| method_code.instructions.as_ref().err().map(|err| format!("metode {} ni mogoče razčleniti: {}", method_display_name(class_file, method_code.method), err)))
+        .collect();
+}
+
+fn stack_map_table_problems(class_file: &ClassFile, method_codes: &[MethodCode]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for MethodCode { method, code, instructions } in method_codes {
+        let has_stack_map_table = code.attributes.iter().any(|attribute| matches!(attribute, AttributeInfo::StackMapTable(_)));
+        if class_file.major_version < STACK_MAP_TABLE_MIN_MAJOR_VERSION {
+            if has_stack_map_table {
+                problems.push(format!("metoda {} ima StackMapTable, čeprav ga verzija {} ne podpira", method_display_name(class_file, method), class_file.major_version));
+            }
+            continue;
+        }
+
+        let Ok(instructions) = instructions else {
+            continue;
+        };
+
+        let requires_frames = !code.exception_table.is_empty() || instructions.iter().any(|instruction| !instruction.branch_targets().is_empty());
+        if requires_frames && !has_stack_map_table {
+            problems.push(format!("metoda {} ima skoke ali exception handlerje, nima pa StackMapTable", method_display_name(class_file, method)));
+        }
+    }
+
+    return problems;
+}
+
+fn switch_padding_problems(class_file: &ClassFile, method_codes: &[MethodCode]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for MethodCode { method, code, instructions } in method_codes {
+        let Ok(instructions) = instructions else {
+            continue;
+        };
+
+        for instruction in instructions {
+            if !matches!(instruction.opcode, Opcode::Tableswitch | Opcode::Lookupswitch) {
+                continue;
+            }
+
+            let padding_start = instruction.offset as usize + 1;
+            let padding = &code.code[padding_start..padding_start + switch_padding_len(instruction.offset) as usize];
+            if padding.iter().any(|&byte| byte != 0) {
+                problems.push(format!("metoda {} ima pri {} na odmiku {} neničelno poravnavo {:?}",
+                    method_display_name(class_file, method), instruction.opcode.mnemonic(), instruction.offset, padding));
+            }
+        }
+    }
+
+    return problems;
+}
+
+/**
+ * Preveri, da ima Code atribut s skoki ali exception handlerji StackMapTable, kadar ga verzija classa zahteva (50+),
+ * in da Code atributi v classih pred verzijo 50 StackMapTable nimajo. Parser tega ne preverja, funkcijo je treba poklicati posebej.
+ * Vrne opise vseh najdenih napak, vključno z metodami, katerih kode ni mogoče razčleniti.
+ */
+pub fn check_stack_map_tables(class_file: &ClassFile) -> Vec<String> {
+    let method_codes = disassemble_methods(class_file);
+    return [undecodable_code_problems(class_file, &method_codes), stack_map_table_problems(class_file, &method_codes)].concat();
+}
+
+/**
+ * Preveri, da so bajti poravnave pred operandi tableswitch in lookupswitch ničelni
+ * (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-6.html#jvms-6.5.tableswitch). Število bajtov poravnave je
+ * določeno z odmikom ukaza, zato se napačno število bajtov pokaže kot neničelna poravnava ali kot koda, ki je ni mogoče
+ * razčleniti. Večina JVM-jev vsebine poravnave ne preverja. Vrne opise vseh najdenih napak.
+ */
+pub fn check_switch_padding(class_file: &ClassFile) -> Vec<String> {
+    let method_codes = disassemble_methods(class_file);
+    return [undecodable_code_problems(class_file, &method_codes), switch_padding_problems(class_file, &method_codes)].concat();
+}
+
+fn constant_kind_name(constant: &CpInfo) -> &'static str {
+    match constant {
+        CpInfo::Utf8(_) => "Utf8",
+        CpInfo::Integer(_) => "Integer",
+        CpInfo::Float(_) => "Float",
+        CpInfo::Long(_) => "Long",
+        CpInfo::Double(_) => "Double",
+        CpInfo::Class(_) => "Class",
+        CpInfo::String(_) => "String",
+        CpInfo::FieldRef(_) => "Fieldref",
+        CpInfo::MethodRef(_) => "Methodref",
+        CpInfo::InterfaceMethodRef(_) => "InterfaceMethodref",
+        CpInfo::NameAndType(_) => "NameAndType",
+        CpInfo::MethodHandle(_) => "MethodHandle",
+        CpInfo::MethodType(_) => "MethodType",
+        CpInfo::Dynamic(_) => "Dynamic",
+        CpInfo::InvokeDynamic(_) => "InvokeDynamic",
+        CpInfo::Module(_) => "Module",
+        CpInfo::Package(_) => "Package",
+    }
+}
+
+/**
+ * Preveri, da se vrsta konstante, na katero kaže ConstantValue atribut polja, ujema s tipom polja
+ * (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.7.2). Vrne opise vseh najdenih napak.
+ */
+pub fn check_constant_values(class_file: &ClassFile) -> Vec<String> {
+    let mut problems = Vec::new();
+    for field in &class_file.fields {
+        let field_name = class_file.get_utf8(field.name_index).unwrap_or("?");
+        for attribute in &field.attributes {
+            let AttributeInfo::ConstantValue(constant_value) = attribute else {
+                continue;
+            };
+
+            let Some(field_type) = field.field_type(class_file) else {
+                problems.push(format!("polje {} ima ConstantValue, njegovega deskriptorja pa ni mogoče razčleniti", field_name));
+                continue;
+            };
+
+            let Some(constant) = class_file.get_constant(constant_value.constantvalue_index) else {
+                problems.push(format!("ConstantValue polja {} kaže na neobstoječ vnos {}", field_name, constant_value.constantvalue_index));
+                continue;
+            };
+
+            let matches = match &field_type {
+                FieldType::Int | FieldType::Short | FieldType::Char | FieldType::Byte | FieldType::Boolean => matches!(constant, CpInfo::Integer(_)),
+                FieldType::Long => matches!(constant, CpInfo::Long(_)),
+                FieldType::Float => matches!(constant, CpInfo::Float(_)),
+                FieldType::Double => matches!(constant, CpInfo::Double(_)),
+                FieldType::Object(class_name) if class_name == "java/lang/String" => matches!(constant, CpInfo::String(_)),
+                _ => {
+                    problems.push(format!("polje {} tipa {} ne more imeti ConstantValue", field_name, field_type));
+                    continue;
+                }
+            };
+
+            if !matches {
+                problems.push(format!("polje {} tipa {} ima ConstantValue vrste {}", field_name, field_type, constant_kind_name(constant)));
+            }
+        }
+    }
+
+    return problems;
+}
+
+/** Struktura, ki ima atribute. */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeOwner {
+    ClassFile,
+    Field,
+    Method,
+    Code,
+    RecordComponent,
+}
+
+impl Display for AttributeOwner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeOwner::ClassFile => write!(f, "ClassFile"),
+            AttributeOwner::Field => write!(f, "field_info"),
+            AttributeOwner::Method => write!(f, "method_info"),
+            AttributeOwner::Code => write!(f, "Code"),
+            AttributeOwner::RecordComponent => write!(f, "record_component_info"),
+        }
+    }
+}
+
+/** Strukture, v katerih se atribut sme pojaviti (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.7-320). */
+pub const ATTRIBUTE_LOCATIONS: &[(&str, &[AttributeOwner])] = &[
+    ("ConstantValue", &[AttributeOwner::Field]),
+    ("Code", &[AttributeOwner::Method]),
+    ("StackMapTable", &[AttributeOwner::Code]),
+    ("Exceptions", &[AttributeOwner::Method]),
+    ("InnerClasses", &[AttributeOwner::ClassFile]),
+    ("EnclosingMethod", &[AttributeOwner::ClassFile]),
+    ("Synthetic", &[AttributeOwner::ClassFile, AttributeOwner::Field, AttributeOwner::Method]),
+    ("Signature", &[AttributeOwner::ClassFile, AttributeOwner::Field, AttributeOwner::Method, AttributeOwner::RecordComponent]),
+    ("SourceFile", &[AttributeOwner::ClassFile]),
+    ("SourceDebugExtension", &[AttributeOwner::ClassFile]),
+    ("LineNumberTable", &[AttributeOwner::Code]),
+    ("LocalVariableTable", &[AttributeOwner::Code]),
+    ("LocalVariableTypeTable", &[AttributeOwner::Code]),
+    ("Deprecated", &[AttributeOwner::ClassFile, AttributeOwner::Field, AttributeOwner::Method]),
+    ("RuntimeVisibleAnnotations", &[AttributeOwner::ClassFile, AttributeOwner::Field, AttributeOwner::Method, AttributeOwner::RecordComponent]),
+    ("RuntimeInvisibleAnnotations", &[AttributeOwner::ClassFile, AttributeOwner::Field, AttributeOwner::Method, AttributeOwner::RecordComponent]),
+    ("RuntimeVisibleParameterAnnotations", &[AttributeOwner::Method]),
+    ("RuntimeInvisibleParameterAnnotations", &[AttributeOwner::Method]),
+    ("RuntimeVisibleTypeAnnotations", &[AttributeOwner::ClassFile, AttributeOwner::Field, AttributeOwner::Method, AttributeOwner::Code, AttributeOwner::RecordComponent]),
+    ("RuntimeInvisibleTypeAnnotations", &[AttributeOwner::ClassFile, AttributeOwner::Field, AttributeOwner::Method, AttributeOwner::Code, AttributeOwner::RecordComponent]),
+    ("AnnotationDefault", &[AttributeOwner::Method]),
+    ("BootstrapMethods", &[AttributeOwner::ClassFile]),
+    ("MethodParameters", &[AttributeOwner::Method]),
+    ("Module", &[AttributeOwner::ClassFile]),
+    ("ModulePackages", &[AttributeOwner::ClassFile]),
+    ("ModuleMainClass", &[AttributeOwner::ClassFile]),
+    ("NestHost", &[AttributeOwner::ClassFile]),
+    ("NestMembers", &[AttributeOwner::ClassFile]),
+    ("Record", &[AttributeOwner::ClassFile]),
+    ("PermittedSubclasses", &[AttributeOwner::ClassFile]),
+];
+
+/** Preveri, da so vsi atributi v strukturah, kjer so dovoljeni (glej ATTRIBUTE_LOCATIONS). Vrne opise vseh najdenih napak. */
+pub fn check_attribute_locations(class_file: &ClassFile) -> Vec<String> {
+    let mut problems = Vec::new();
+    check_attributes_owner(&class_file.attributes, AttributeOwner::ClassFile, "class", &mut problems);
+    for field in &class_file.fields {
+        let owner_name = format!("polje {}", class_file.get_utf8(field.name_index).unwrap_or("?"));
+        check_attributes_owner(&field.attributes, AttributeOwner::Field, &owner_name, &mut problems);
+    }
+    for method in &class_file.methods {
+        let owner_name = format!("metoda {}", method_display_name(class_file, method));
+        check_attributes_owner(&method.attributes, AttributeOwner::Method, &owner_name, &mut problems);
+    }
+
+    return problems;
+}
+
+/** Preveri atribute ene strukture ter atribute Code atributov in komponent recorda v njej. */
+fn check_attributes_owner(attributes: &[AttributeInfo], owner: AttributeOwner, owner_name: &str, problems: &mut Vec<String>) {
+    for attribute in attributes {
+        let allowed = ATTRIBUTE_LOCATIONS.iter().any(|(name, owners)| *name == attribute.name() && owners.contains(&owner));
+        if !allowed {
+            problems.push(format!("{} ima atribut {}, ki v {} ni dovoljen", owner_name, attribute.name(), owner));
+        }
+
+        match attribute {
+            AttributeInfo::Code(attribute_code) => {
+                check_attributes_owner(&attribute_code.attributes, AttributeOwner::Code, &format!("Code ({})", owner_name), problems);
+            },
+            AttributeInfo::Record(attribute_record) => {
+                for (i, component) in attribute_record.components.iter().enumerate() {
+                    check_attributes_owner(&component.attributes, AttributeOwner::RecordComponent, &format!("komponenta recorda {} ({})", i, owner_name), problems);
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+/** Ali je ime veljavno unqualified name (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.2.2). */
+fn is_valid_unqualified_name(name: &str) -> bool {
+    return !name.is_empty() && !name.contains(['.', ';', '[', '/']);
+}
+
+/**
+ * Preveri, da so imena polj in metod veljavna unqualified names. Imena metod poleg tega ne smejo vsebovati `<` in `>`,
+ * razen `<init>` in `<clinit>`, ki sta rezervirani za metode in kot imeni polj nista dovoljeni. Vrne opise vseh najdenih napak.
+ */
+pub fn check_member_names(class_file: &ClassFile) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (i, field) in class_file.fields.iter().enumerate() {
+        match class_file.get_utf8(field.name_index) {
+            Some(name) if !is_valid_unqualified_name(name) || matches!(name, "<init>" | "<clinit>") => problems.push(format!("polje {} ima neveljavno ime \"{}\"", i, name)),
+            Some(_) => (),
+            None => problems.push(format!("name_index {} polja {} ne kaže na CpUtf8", field.name_index, i)),
+        };
+    }
+
+    for (i, method) in class_file.methods.iter().enumerate() {
+        match class_file.get_utf8(method.name_index) {
+            Some("<init>" | "<clinit>") => (),
+            Some(name) if !is_valid_unqualified_name(name) || name.contains(['<', '>']) => problems.push(format!("metoda {} ima neveljavno ime \"{}\"", i, name)),
+            Some(_) => (),
+            None => problems.push(format!("name_index {} metode {} ne kaže na CpUtf8", method.name_index, i)),
+        };
+    }
+
+    return problems;
+}
+
+/**
+ * Preveri EnclosingMethod atribut (https://docs.oracle.com/javase/specs/jvms/se22/html/jvms-4.html#jvms-4.7.7): class ga
+ * ima največ enega, class_index kaže na CpClass, method_index pa je 0 ali kaže na CpNameAndType. Ali je class lokalen ali
+ * anonimen, iz enega classa ni mogoče zanesljivo ugotoviti, glej check_enclosing_method_inner_class. Vrne opise vseh
+ * najdenih napak.
+ */
+pub fn check_enclosing_method(class_file: &ClassFile) -> Vec<String> {
+    let mut problems = Vec::new();
+    let enclosing_methods: Vec<&AttributeEnclosingMethod> = class_file.attributes.iter().filter_map(|attribute| match attribute {
+        AttributeInfo::EnclosingMethod(attribute_enclosing_method) => Some(attribute_enclosing_method),
+        _ => None,
+    }).collect();
+
+    let Some(enclosing_method) = enclosing_methods.first() else {
+        return problems;
+    };
+    if enclosing_methods.len() > 1 {
+        problems.push(format!("class ima {} EnclosingMethod atributov, dovoljen je največ eden", enclosing_methods.len()));
+    }
+
+    if class_file.get_class_name(enclosing_method.class_index).is_none() {
+        problems.push(format!("EnclosingMethod class_index {} ne kaže na CpClass", enclosing_method.class_index));
+    }
+    if enclosing_method.method_index != 0 && class_file.get_name_and_type(enclosing_method.method_index).is_none() {
+        problems.push(format!("EnclosingMethod method_index {} ne kaže na CpNameAndType", enclosing_method.method_index));
+    }
+
+    return problems;
+}
+
+/**
+ * Preveri, da je class z EnclosingMethod atributom naveden v svojem InnerClasses atributu brez zunanjega razreda
+ * (outer_class_info_index je 0), kot ga za lokalne in anonimne razrede zapiše prevajalnik. Specifikacija tega ne zahteva,
+ * zato je to le hevristika. Vrne opise vseh najdenih težav.
+ */
+pub fn check_enclosing_method_inner_class(class_file: &ClassFile) -> Vec<String> {
+    let mut problems = Vec::new();
+    if !class_file.attributes.iter().any(|attribute| matches!(attribute, AttributeInfo::EnclosingMethod(_))) {
+        return problems;
+    }
+
+    let inner_class_entry = class_file.attributes.iter().find_map(|attribute| match attribute {
+        AttributeInfo::InnerClasses(attribute_inner_classes) => attribute_inner_classes.classes.iter().find(|entry| entry.inner_class_info_index == class_file.this_class),
+        _ => None,
+    });
+    match inner_class_entry {
+        None => problems.push("class z EnclosingMethod ni naveden v svojem InnerClasses atributu, torej ni lokalen ali anonimen".into()),
+        Some(entry) if entry.outer_class_info_index != 0 => {
+            problems.push(format!("class z EnclosingMethod je v InnerClasses naveden kot član razreda {}, torej ni lokalen ali anonimen",
+                class_file.get_class_name(entry.outer_class_info_index).unwrap_or("?")));
+        },
+        Some(_) => (),
+    }
+
+    return problems;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /** Class je veljaven, a verjetno ni tak, kot bi moral biti (npr. neuporabljene konstante). */
+    Warning,
+    /** Class krši specifikacijo in ga JVM ne bi naložil oz. verificiral. */
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Warning => write!(f, "opozorilo: {}", self.message),
+            Severity::Error => write!(f, "napaka: {}", self.message),
+        }
+    }
+}
+
+/** Nastavitve verify. Privzeto so vklopljeni vsi validatorji razen tistih, ki jih je treba izrecno zahtevati. */
+#[derive(Debug, Clone, Default)]
+pub struct VerifyOptions {
+    /** Požene tudi check_stack_map_tables. */
+    pub check_stack_map_tables: bool,
+}
+
+/**
+ * Požene validatorje s privzetimi nastavitvami in vrne vse najdene težave. Za CI zadostuje preveriti, ali je med njimi
+ * kakšna s Severity::Error.
+ */
+pub fn verify(class_file: &ClassFile) -> Vec<Diagnostic> {
+    return verify_with_options(class_file, &VerifyOptions::default());
+}
+
+/**
+ * Kršitve specifikacije so Severity::Error, ugotovitve hevrističnih validatorjev (neničelna poravnava switch ukazov,
+ * EnclosingMethod brez ustreznega vnosa v InnerClasses, neuporabljene konstante) pa Severity::Warning. Koda vsake
+ * metode se razčleni le enkrat, metoda, katere kode ni mogoče razčleniti, pa je ena sama Severity::Error.
+ */
+pub fn verify_with_options(class_file: &ClassFile, options: &VerifyOptions) -> Vec<Diagnostic> {
+    let method_codes = disassemble_methods(class_file);
+    let mut checks = vec![
+        (Severity::Error, check_constant_values(class_file)),
+        (Severity::Error, check_member_names(class_file)),
+        (Severity::Error, check_attribute_locations(class_file)),
+        (Severity::Error, check_enclosing_method(class_file)),
+        (Severity::Error, undecodable_code_problems(class_file, &method_codes)),
+        (Severity::Warning, switch_padding_problems(class_file, &method_codes)),
+        (Severity::Warning, check_enclosing_method_inner_class(class_file)),
+    ];
+    if options.check_stack_map_tables {
+        checks.push((Severity::Error, stack_map_table_problems(class_file, &method_codes)));
+    }
+
+    let mut diagnostics: Vec<Diagnostic> = checks.into_iter()
+        .flat_map(|(severity, problems)| problems.into_iter().map(move |message| Diagnostic { severity, message }))
+        .collect();
+
+    match class_file.unreferenced_constants() {
+        Ok(unreferenced_constants) if !unreferenced_constants.is_empty() => {
+            diagnostics.push(Diagnostic { severity: Severity::Warning, message: format!("constant pool vsebuje {} neuporabljenih vnosov: {:?}", unreferenced_constants.len(), unreferenced_constants) });
+        },
+        Ok(_) => (),
+        Err(err) => diagnostics.push(Diagnostic { severity: Severity::Error, message: format!("neuporabljenih vnosov constant poola ni mogoče določiti: {}", err) }),
+    };
+
+    return diagnostics;
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::fixture::{u16s, ClassBuilder};
+
+    use super::*;
+
+    #[test]
+    fn verify_reports_when_unreferenced_constants_cannot_be_determined() {
+        let mut builder = ClassBuilder::new("A");
+        let code = builder.code(1, 0, &[0x11, 0x00], &[], &[]);
+        builder.method(0x0008, "<clinit>", "()V", &[code]);
+
+        let diagnostics = verify(&builder.parse());
+
+        assert!(diagnostics.iter().any(|diagnostic| diagnostic.severity == Severity::Error && diagnostic.message.starts_with("neuporabljenih vnosov")));
+    }
+
+    #[test]
+    fn verify_warns_about_unreferenced_constants() {
+        let mut builder = ClassBuilder::new("A");
+        let unused = builder.utf8("unused");
+
+        let diagnostics = verify(&builder.parse());
+
+        assert_eq!(diagnostics, vec![Diagnostic { severity: Severity::Warning, message: format!("constant pool vsebuje 1 neuporabljenih vnosov: [{}]", unused) }]);
+    }
+
+    /**
+     * Class z več napakami: neveljavno ime polja, ConstantValue napačne vrste, SourceFile na metodi, neničelna poravnava
+     * tableswitch brez StackMapTable ter EnclosingMethod brez vnosa v InnerClasses.
+     */
+    fn defective_class() -> ClassBuilder {
+        let mut builder = ClassBuilder::new("p/A$1");
+        builder.field(0x0002, "a.b", "I", &[]);
+        let string = builder.string("x");
+        let constant_value = builder.attribute("ConstantValue", &u16s(&[string]));
+        builder.field(0x0018, "X", "I", &[constant_value]);
+
+        // iload_0, tableswitch s poravnavo [1, 0] in obema skokoma na return
+        let code = builder.code(1, 1, &[0x1a, 0xaa, 0x01, 0x00, 0, 0, 0, 19, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 19, 0xb1], &[], &[]);
+        let source_file = builder.utf8("A.java");
+        let source_file = builder.attribute("SourceFile", &u16s(&[source_file]));
+        builder.method(0x0008, "s", "(I)V", &[code, source_file]);
+
+        let outer_class = builder.class("p/A");
+        let enclosing_method = builder.attribute("EnclosingMethod", &u16s(&[outer_class, 0]));
+        builder.class_attribute(enclosing_method);
+
+        return builder;
+    }
+
+    fn messages(diagnostics: &[Diagnostic], severity: Severity) -> Vec<&str> {
+        return diagnostics.iter().filter(|diagnostic| diagnostic.severity == severity).map(|diagnostic| diagnostic.message.as_str()).collect();
+    }
+
+    #[test]
+    fn verify_assigns_severity_per_check() {
+        let diagnostics = verify(&defective_class().parse());
+
+        assert_eq!(messages(&diagnostics, Severity::Error), [
+            "polje X tipa int ima ConstantValue vrste String",
+            "polje 0 ima neveljavno ime \"a.b\"",
+            "metoda s(I)V ima atribut SourceFile, ki v method_info ni dovoljen",
+        ]);
+        assert_eq!(messages(&diagnostics, Severity::Warning), [
+            "metoda s(I)V ima pri tableswitch na odmiku 1 neničelno poravnavo [1, 0]",
+            "class z EnclosingMethod ni naveden v svojem InnerClasses atributu, torej ni lokalen ali anonimen",
+        ]);
+    }
+
+    #[test]
+    fn stack_map_table_check_is_opt_in() {
+        let class_file = defective_class().parse();
+        let options = VerifyOptions { check_stack_map_tables: true };
+
+        let diagnostics = verify_with_options(&class_file, &options);
+
+        assert!(!verify(&class_file).iter().any(|diagnostic| diagnostic.message.contains("StackMapTable")));
+        assert!(messages(&diagnostics, Severity::Error).contains(&"metoda s(I)V ima skoke ali exception handlerje, nima pa StackMapTable"));
+    }
+
+    #[test]
+    fn stack_map_table_is_not_allowed_before_version_50() {
+        let mut builder = ClassBuilder::new("A");
+        builder.major_version = 49;
+        let stack_map_table = builder.attribute("StackMapTable", &u16s(&[0]));
+        let code = builder.code(0, 0, &[0xb1], &[], &[stack_map_table]);
+        builder.method(0x0008, "m", "()V", &[code]);
+
+        assert_eq!(check_stack_map_tables(&builder.parse()), ["metoda m()V ima StackMapTable, čeprav ga verzija 49 ne podpira"]);
+    }
+
+    #[test]
+    fn enclosing_method_must_point_to_class() {
+        let mut builder = ClassBuilder::new("A");
+        let integer = builder.integer(1);
+        let enclosing_method = builder.attribute("EnclosingMethod", &u16s(&[integer, 0]));
+        builder.class_attribute(enclosing_method.clone());
+        builder.class_attribute(enclosing_method);
+
+        assert_eq!(check_enclosing_method(&builder.parse()), [
+            "class ima 2 EnclosingMethod atributov, dovoljen je največ eden".to_string(),
+            format!("EnclosingMethod class_index {} ne kaže na CpClass", integer),
+        ]);
+    }
+
+    #[test]
+    fn member_names_must_be_unqualified() {
+        let mut builder = ClassBuilder::new("A");
+        builder.field(0x0002, "ok", "I", &[]);
+        builder.field(0x0002, "", "I", &[]);
+        builder.field(0x0002, "a[b", "I", &[]);
+        builder.method(0x0001, "<init>", "()V", &[]);
+        builder.method(0x0008, "<clinit>", "()V", &[]);
+        builder.method(0x0001, "<main>", "()V", &[]);
+        builder.method(0x0001, "a/b", "()V", &[]);
+        builder.method(0x0001, "$ok", "()V", &[]);
+
+        assert_eq!(check_member_names(&builder.parse()), [
+            "polje 1 ima neveljavno ime \"\"",
+            "polje 2 ima neveljavno ime \"a[b\"",
+            "metoda 2 ima neveljavno ime \"<main>\"",
+            "metoda 3 ima neveljavno ime \"a/b\"",
+        ]);
+    }
+
+    #[test]
+    fn attribute_locations_per_owner() {
+        let mut builder = ClassBuilder::new("A");
+        let integer = builder.integer(1);
+        let constant_value = builder.attribute("ConstantValue", &u16s(&[integer]));
+        builder.field(0x0018, "X", "I", core::slice::from_ref(&constant_value));
+        let empty_stack_map_table = builder.attribute("StackMapTable", &u16s(&[0]));
+        let code = builder.code(0, 1, &[0xb1], &[], core::slice::from_ref(&empty_stack_map_table));
+        builder.method(0x0001, "m", "()V", &[code, constant_value.clone(), empty_stack_map_table]);
+        let misplaced_in_code = builder.code(0, 1, &[0xb1], &[], core::slice::from_ref(&constant_value));
+        builder.method(0x0001, "n", "()V", &[misplaced_in_code]);
+
+        let component_name = builder.utf8("x");
+        let component_descriptor = builder.utf8("I");
+        let signature = builder.utf8("I");
+        let signature = builder.attribute("Signature", &u16s(&[signature]));
+        let record = builder.attribute("Record", &[&u16s(&[1, component_name, component_descriptor, 2])[..], &signature, &constant_value].concat());
+        builder.class_attribute(record);
+
+        assert_eq!(check_attribute_locations(&builder.parse()), [
+            "komponenta recorda 0 (class) ima atribut ConstantValue, ki v record_component_info ni dovoljen",
+            "metoda m()V ima atribut ConstantValue, ki v method_info ni dovoljen",
+            "metoda m()V ima atribut StackMapTable, ki v method_info ni dovoljen",
+            "Code (metoda n()V) ima atribut ConstantValue, ki v Code ni dovoljen",
+        ]);
+    }
+
+    #[test]
+    fn semicolon_and_init_names() {
+        let mut builder = ClassBuilder::new("A");
+        builder.field(0x0002, "a;b", "I", &[]);
+        builder.field(0x0002, "<init>", "I", &[]);
+        builder.field(0x0002, "<clinit>", "I", &[]);
+        builder.field(0x0002, "<ok>", "I", &[]);
+        builder.method(0x0001, "a;b", "()V", &[]);
+        builder.method(0x0001, "<init>", "(I)V", &[]);
+
+        assert_eq!(check_member_names(&builder.parse()), [
+            "polje 0 ima neveljavno ime \"a;b\"",
+            "polje 1 ima neveljavno ime \"<init>\"",
+            "polje 2 ima neveljavno ime \"<clinit>\"",
+            "metoda 0 ima neveljavno ime \"a;b\"",
+        ]);
+    }
+
+    #[test]
+    fn member_name_index_must_point_to_utf8() {
+        let mut builder = ClassBuilder::new("A");
+        builder.method(0x0001, "m", "()V", &[]);
+        let mut class_file = builder.parse();
+        class_file.methods[0].name_index = class_file.this_class;
+
+        assert_eq!(check_member_names(&class_file), [format!("name_index {} metode 0 ne kaže na CpUtf8", class_file.this_class)]);
+    }
+
+    /** Statična metoda s(I)V z lookupswitch brez parov na odmiku 1, katerega poravnava sta bajta padding. */
+    fn class_with_lookupswitch(padding: [u8; 2]) -> ClassFile {
+        let mut builder = ClassBuilder::new("A");
+        let code = builder.code(1, 1, &[0x1a, 0xab, padding[0], padding[1], 0, 0, 0, 11, 0, 0, 0, 0, 0xb1], &[], &[]);
+        builder.method(0x0008, "s", "(I)V", &[code]);
+
+        return builder.parse();
+    }
+
+    #[test]
+    fn switch_padding_must_be_zero() {
+        assert!(check_switch_padding(&class_with_lookupswitch([0, 0])).is_empty());
+        assert_eq!(check_switch_padding(&class_with_lookupswitch([0, 0xFF])), ["metoda s(I)V ima pri lookupswitch na odmiku 1 neničelno poravnavo [0, 255]"]);
+    }
+
+    #[test]
+    fn switch_padding_of_malformed_code_is_reported() {
+        let mut builder = ClassBuilder::new("A");
+        // tableswitch brez operandov
+        let code = builder.code(1, 1, &[0x1a, 0xaa, 0, 0], &[], &[]);
+        builder.method(0x0008, "s", "(I)V", &[code]);
+
+        let problems = check_switch_padding(&builder.parse());
+
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("metode s(I)V ni mogoče razčleniti"), "{}", problems[0]);
+    }
+
+    #[test]
+    fn undecodable_code_is_reported_once_as_error() {
+        let mut builder = ClassBuilder::new("A");
+        // tableswitch brez operandov
+        let code = builder.code(1, 1, &[0x1a, 0xaa, 0, 0], &[], &[]);
+        builder.method(0x0008, "s", "(I)V", &[code]);
+        let class_file = builder.parse();
+
+        let diagnostics = verify_with_options(&class_file, &VerifyOptions { check_stack_map_tables: true });
+
+        let undecodable: Vec<_> = diagnostics.iter().filter(|diagnostic| diagnostic.message.contains("ni mogoče razčleniti")).collect();
+        assert_eq!(undecodable.len(), 1, "{:?}", diagnostics);
+        assert_eq!(undecodable[0].severity, Severity::Error);
+        assert!(undecodable[0].message.starts_with("metode s(I)V ni mogoče razčleniti"), "{}", undecodable[0].message);
+        assert_eq!(check_stack_map_tables(&class_file), check_switch_padding(&class_file));
+    }
+}
@@ -0,0 +1,677 @@
+use core::{error, fmt::{self, Display, Formatter}};
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use byteorder::BigEndian;
+
+use crate::{io::WriteBytesExt, parser::{AttributeAnnotationsElementValue, AttributeInfo, AttributeRuntimeAnnotationsEntry, AttributeRuntimeTypeAnnotationsEntry, AttributeRuntimeTypeAnnotationsEntryTargetInfo, ClassFile, CpInfo, StackMapFrame, VerificationTypeInfo}};
+
+#[derive(Debug, Clone)]
+pub struct WriteError {
+    msg: String,
+}
+
+impl Display for WriteError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Napaka pri zapisu classa: {}.", self.msg)
+    }
+}
+
+impl error::Error for WriteError {}
+
+impl ClassFile {
+    /**
+     * Zapiše class v obliki class datoteke. Števci in attribute_length se izračunajo iz vsebine, zato se shranjeni
+     * attribute_length ne upošteva. Ime atributa se zapiše kot indeks prvega CpUtf8 z imenom atributa, ki mora
+     * obstajati v constant poolu. Pri StackMapTable, prebranem z ParseOptions::lenient, se zapišejo le prebrani okvirji.
+     */
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Box<dyn error::Error>> {
+        let mut attribute_name_indices = BTreeMap::new();
+        for (index, entry) in self.constant_pool.iter().enumerate().skip(1) {
+            if let CpInfo::Utf8(cp_utf8) = entry {
+                attribute_name_indices.entry(cp_utf8.converted.as_str()).or_insert(index as u16);
+            }
+        }
+
+        let mut bytes = Vec::new();
+        bytes.write_u32::<BigEndian>(self.magic)?;
+        bytes.write_u16::<BigEndian>(self.minor_version)?;
+        bytes.write_u16::<BigEndian>(self.major_version)?;
+
+        write_count(&mut bytes, self.constant_pool.len(), "constant_pool_count")?;
+        let mut index = 1;
+        while index < self.constant_pool.len() {
+            let entry = &self.constant_pool[index];
+            write_constant_pool_entry(&mut bytes, entry)?;
+
+            // drugi vnos Long in Double je "dummy" in se ne zapiše
+            index += if matches!(entry, CpInfo::Long(_) | CpInfo::Double(_)) { 2 } else { 1 };
+        }
+
+        bytes.write_u16::<BigEndian>(self.access_flags)?;
+        bytes.write_u16::<BigEndian>(self.this_class)?;
+        bytes.write_u16::<BigEndian>(self.super_class)?;
+        write_u16_table(&mut bytes, &self.interfaces, "interfaces_count")?;
+
+        write_count(&mut bytes, self.fields.len(), "fields_count")?;
+        for field in &self.fields {
+            bytes.write_u16::<BigEndian>(field.access_flags)?;
+            bytes.write_u16::<BigEndian>(field.name_index)?;
+            bytes.write_u16::<BigEndian>(field.descriptor_index)?;
+            write_attributes(&mut bytes, &field.attributes, &attribute_name_indices)?;
+        }
+
+        write_count(&mut bytes, self.methods.len(), "methods_count")?;
+        for method in &self.methods {
+            bytes.write_u16::<BigEndian>(method.access_flags)?;
+            bytes.write_u16::<BigEndian>(method.name_index)?;
+            bytes.write_u16::<BigEndian>(method.descriptor_index)?;
+            write_attributes(&mut bytes, &method.attributes, &attribute_name_indices)?;
+        }
+
+        write_attributes(&mut bytes, &self.attributes, &attribute_name_indices)?;
+
+        return Ok(bytes);
+    }
+}
+
+fn write_count(bytes: &mut Vec<u8>, count: usize, count_name: &str) -> Result<(), Box<dyn error::Error>> {
+    let Ok(count) = u16::try_from(count) else {
+        return Err(WriteError { msg: format!("{} {} presega 65535", count_name, count) }.into());
+    };
+    bytes.write_u16::<BigEndian>(count)?;
+
+    return Ok(());
+}
+
+fn write_count_u8(bytes: &mut Vec<u8>, count: usize, count_name: &str) -> Result<(), Box<dyn error::Error>> {
+    let Ok(count) = u8::try_from(count) else {
+        return Err(WriteError { msg: format!("{} {} presega 255", count_name, count) }.into());
+    };
+    bytes.write_u8(count)?;
+
+    return Ok(());
+}
+
+fn write_u16_table(bytes: &mut Vec<u8>, table: &[u16], count_name: &str) -> Result<(), Box<dyn error::Error>> {
+    write_count(bytes, table.len(), count_name)?;
+    for &value in table {
+        bytes.write_u16::<BigEndian>(value)?;
+    }
+
+    return Ok(());
+}
+
+fn write_constant_pool_entry(bytes: &mut Vec<u8>, entry: &CpInfo) -> Result<(), Box<dyn error::Error>> {
+    bytes.write_u8(entry.tag())?;
+    match entry {
+        CpInfo::Utf8(cp_utf8) => {
+            write_count(bytes, cp_utf8.bytes.len(), "CpUtf8 length")?;
+            bytes.extend_from_slice(&cp_utf8.bytes);
+        },
+        CpInfo::Integer(cp_integer) => bytes.extend_from_slice(&cp_integer.bytes),
+        CpInfo::Float(cp_float) => bytes.extend_from_slice(&cp_float.bytes),
+        CpInfo::Long(cp_long) => {
+            bytes.write_u32::<BigEndian>(cp_long.high_bytes)?;
+            bytes.write_u32::<BigEndian>(cp_long.low_bytes)?;
+        },
+        CpInfo::Double(cp_double) => {
+            bytes.write_u32::<BigEndian>(cp_double.high_bytes)?;
+            bytes.write_u32::<BigEndian>(cp_double.low_bytes)?;
+        },
+        CpInfo::Class(cp_class) => bytes.write_u16::<BigEndian>(cp_class.name_index)?,
+        CpInfo::String(cp_string) => bytes.write_u16::<BigEndian>(cp_string.string_index)?,
+        CpInfo::FieldRef(cp_field_ref) => {
+            bytes.write_u16::<BigEndian>(cp_field_ref.class_index)?;
+            bytes.write_u16::<BigEndian>(cp_field_ref.name_and_type_index)?;
+        },
+        CpInfo::MethodRef(cp_method_ref) => {
+            bytes.write_u16::<BigEndian>(cp_method_ref.class_index)?;
+            bytes.write_u16::<BigEndian>(cp_method_ref.name_and_type_index)?;
+        },
+        CpInfo::InterfaceMethodRef(cp_interface_method_ref) => {
+            bytes.write_u16::<BigEndian>(cp_interface_method_ref.class_index)?;
+            bytes.write_u16::<BigEndian>(cp_interface_method_ref.name_and_type_index)?;
+        },
+        CpInfo::NameAndType(cp_name_and_type) => {
+            bytes.write_u16::<BigEndian>(cp_name_and_type.name_index)?;
+            bytes.write_u16::<BigEndian>(cp_name_and_type.descriptor_index)?;
+        },
+        CpInfo::MethodHandle(cp_method_handle) => {
+            bytes.write_u8(cp_method_handle.reference_kind as u8)?;
+            bytes.write_u16::<BigEndian>(cp_method_handle.reference_index)?;
+        },
+        CpInfo::MethodType(cp_method_type) => bytes.write_u16::<BigEndian>(cp_method_type.descriptor_index)?,
+        CpInfo::Dynamic(cp_dynamic) => {
+            bytes.write_u16::<BigEndian>(cp_dynamic.bootstrap_method_attr_index)?;
+            bytes.write_u16::<BigEndian>(cp_dynamic.name_and_type_index)?;
+        },
+        CpInfo::InvokeDynamic(cp_invoke_dynamic) => {
+            bytes.write_u16::<BigEndian>(cp_invoke_dynamic.bootstrap_method_attr_index)?;
+            bytes.write_u16::<BigEndian>(cp_invoke_dynamic.name_and_type_index)?;
+        },
+        CpInfo::Module(cp_module) => bytes.write_u16::<BigEndian>(cp_module.name_index)?,
+        CpInfo::Package(cp_package) => bytes.write_u16::<BigEndian>(cp_package.name_index)?,
+    };
+
+    return Ok(());
+}
+
+fn write_attributes(bytes: &mut Vec<u8>, attributes: &[AttributeInfo], attribute_name_indices: &BTreeMap<&str, u16>) -> Result<(), Box<dyn error::Error>> {
+    write_count(bytes, attributes.len(), "attributes_count")?;
+    for attribute in attributes {
+        write_attribute(bytes, attribute, attribute_name_indices)?;
+    }
+
+    return Ok(());
+}
+
+fn write_attribute(bytes: &mut Vec<u8>, attribute: &AttributeInfo, attribute_name_indices: &BTreeMap<&str, u16>) -> Result<(), Box<dyn error::Error>> {
+    let Some(&attribute_name_index) = attribute_name_indices.get(attribute.name()) else {
+        return Err(WriteError { msg: format!("constant pool nima CpUtf8 z imenom atributa {}", attribute.name()) }.into());
+    };
+    bytes.write_u16::<BigEndian>(attribute_name_index)?;
+
+    // attribute_length se vpiše, ko je vsebina zapisana
+    let attribute_length_position = bytes.len();
+    bytes.write_u32::<BigEndian>(0)?;
+    write_attribute_info(bytes, attribute, attribute_name_indices)?;
+
+    let Ok(attribute_length) = u32::try_from(bytes.len() - attribute_length_position - 4) else {
+        return Err(WriteError { msg: format!("atribut {} je daljši od 4GB", attribute.name()) }.into());
+    };
+    bytes[attribute_length_position..attribute_length_position + 4].copy_from_slice(&attribute_length.to_be_bytes());
+
+    return Ok(());
+}
+
+fn write_attribute_info(bytes: &mut Vec<u8>, attribute: &AttributeInfo, attribute_name_indices: &BTreeMap<&str, u16>) -> Result<(), Box<dyn error::Error>> {
+    match attribute {
+        AttributeInfo::ConstantValue(attribute_constant_value) => bytes.write_u16::<BigEndian>(attribute_constant_value.constantvalue_index)?,
+        AttributeInfo::Code(attribute_code) => {
+            bytes.write_u16::<BigEndian>(attribute_code.max_stack)?;
+            bytes.write_u16::<BigEndian>(attribute_code.max_locals)?;
+            let Ok(code_length) = u32::try_from(attribute_code.code.len()) else {
+                return Err(WriteError { msg: format!("code_length {} presega 4GB", attribute_code.code.len()) }.into());
+            };
+            bytes.write_u32::<BigEndian>(code_length)?;
+            bytes.extend_from_slice(&attribute_code.code);
+
+            write_count(bytes, attribute_code.exception_table.len(), "exception_table_length")?;
+            for entry in &attribute_code.exception_table {
+                bytes.write_u16::<BigEndian>(entry.start_pc)?;
+                bytes.write_u16::<BigEndian>(entry.end_pc)?;
+                bytes.write_u16::<BigEndian>(entry.handler_pc)?;
+                bytes.write_u16::<BigEndian>(entry.catch_type)?;
+            }
+
+            write_attributes(bytes, &attribute_code.attributes, attribute_name_indices)?;
+        },
+        AttributeInfo::StackMapTable(attribute_stack_map_table) => {
+            write_count(bytes, attribute_stack_map_table.entries.len(), "number_of_entries")?;
+            for frame in &attribute_stack_map_table.entries {
+                write_stack_map_frame(bytes, frame)?;
+            }
+        },
+        AttributeInfo::Exceptions(attribute_exceptions) => write_u16_table(bytes, &attribute_exceptions.exception_index_table, "number_of_exceptions")?,
+        AttributeInfo::InnerClasses(attribute_inner_classes) => {
+            write_count(bytes, attribute_inner_classes.classes.len(), "number_of_classes")?;
+            for class in &attribute_inner_classes.classes {
+                bytes.write_u16::<BigEndian>(class.inner_class_info_index)?;
+                bytes.write_u16::<BigEndian>(class.outer_class_info_index)?;
+                bytes.write_u16::<BigEndian>(class.inner_name_index)?;
+                bytes.write_u16::<BigEndian>(class.inner_class_access_flags)?;
+            }
+        },
+        AttributeInfo::EnclosingMethod(attribute_enclosing_method) => {
+            bytes.write_u16::<BigEndian>(attribute_enclosing_method.class_index)?;
+            bytes.write_u16::<BigEndian>(attribute_enclosing_method.method_index)?;
+        },
+        AttributeInfo::Synthetic(_) | AttributeInfo::Deprecated(_) => (),
+        AttributeInfo::Signature(attribute_signature) => bytes.write_u16::<BigEndian>(attribute_signature.signature_index)?,
+        AttributeInfo::SourceFile(attribute_source_file) => bytes.write_u16::<BigEndian>(attribute_source_file.sourcefile_index)?,
+        AttributeInfo::SourceDebugExtension(attribute_source_debug_extension) => bytes.extend_from_slice(&attribute_source_debug_extension.debug_extension),
+        AttributeInfo::LineNumberTable(attribute_line_number_table) => {
+            write_count(bytes, attribute_line_number_table.line_number_table.len(), "line_number_table_length")?;
+            for entry in &attribute_line_number_table.line_number_table {
+                bytes.write_u16::<BigEndian>(entry.start_pc)?;
+                bytes.write_u16::<BigEndian>(entry.line_number)?;
+            }
+        },
+        AttributeInfo::LocalVariableTable(attribute_local_variable_table) => {
+            write_count(bytes, attribute_local_variable_table.local_variable_table.len(), "local_variable_table_length")?;
+            for entry in &attribute_local_variable_table.local_variable_table {
+                bytes.write_u16::<BigEndian>(entry.start_pc)?;
+                bytes.write_u16::<BigEndian>(entry.length)?;
+                bytes.write_u16::<BigEndian>(entry.name_index)?;
+                bytes.write_u16::<BigEndian>(entry.descriptor_index)?;
+                bytes.write_u16::<BigEndian>(entry.index)?;
+            }
+        },
+        AttributeInfo::LocalVariableTypeTable(attribute_local_variable_type_table) => {
+            write_count(bytes, attribute_local_variable_type_table.local_variable_table.len(), "local_variable_type_table_length")?;
+            for entry in &attribute_local_variable_type_table.local_variable_table {
+                bytes.write_u16::<BigEndian>(entry.start_pc)?;
+                bytes.write_u16::<BigEndian>(entry.length)?;
+                bytes.write_u16::<BigEndian>(entry.name_index)?;
+                bytes.write_u16::<BigEndian>(entry.signature_index)?;
+                bytes.write_u16::<BigEndian>(entry.index)?;
+            }
+        },
+        AttributeInfo::RuntimeVisibleAnnotations(attribute_annotations) => write_annotations(bytes, &attribute_annotations.annotations)?,
+        AttributeInfo::RuntimeInvisibleAnnotations(attribute_annotations) => write_annotations(bytes, &attribute_annotations.annotations)?,
+        AttributeInfo::RuntimeVisibleParameterAnnotations(attribute_parameter_annotations) => write_parameter_annotations(bytes, &attribute_parameter_annotations.parameter_annotations)?,
+        AttributeInfo::RuntimeInvisibleParameterAnnotations(attribute_parameter_annotations) => write_parameter_annotations(bytes, &attribute_parameter_annotations.parameter_annotations)?,
+        AttributeInfo::RuntimeVisibleTypeAnnotations(attribute_type_annotations) => write_type_annotations(bytes, &attribute_type_annotations.annotations)?,
+        AttributeInfo::RuntimeInvisibleTypeAnnotations(attribute_type_annotations) => write_type_annotations(bytes, &attribute_type_annotations.annotations)?,
+        AttributeInfo::AnnotationDefault(attribute_annotation_default) => write_annotations_element_value(bytes, &attribute_annotation_default.default_value)?,
+        AttributeInfo::BootstrapMethods(attribute_bootstrap_methods) => {
+            write_count(bytes, attribute_bootstrap_methods.bootstrap_methods.len(), "num_bootstrap_methods")?;
+            for bootstrap_method in &attribute_bootstrap_methods.bootstrap_methods {
+                bytes.write_u16::<BigEndian>(bootstrap_method.bootstrap_method_ref)?;
+                write_u16_table(bytes, &bootstrap_method.bootstrap_arguments, "num_bootstrap_arguments")?;
+            }
+        },
+        AttributeInfo::MethodParameters(attribute_method_parameters) => {
+            write_count_u8(bytes, attribute_method_parameters.parameters.len(), "parameters_count")?;
+            for parameter in &attribute_method_parameters.parameters {
+                bytes.write_u16::<BigEndian>(parameter.name_index)?;
+                bytes.write_u16::<BigEndian>(parameter.access_flags)?;
+            }
+        },
+        AttributeInfo::Module(attribute_module) => {
+            bytes.write_u16::<BigEndian>(attribute_module.module_name_index)?;
+            bytes.write_u16::<BigEndian>(attribute_module.module_flags)?;
+            bytes.write_u16::<BigEndian>(attribute_module.module_version_index)?;
+
+            write_count(bytes, attribute_module.requires.len(), "requires_count")?;
+            for requires in &attribute_module.requires {
+                bytes.write_u16::<BigEndian>(requires.requires_index)?;
+                bytes.write_u16::<BigEndian>(requires.requires_flags)?;
+                bytes.write_u16::<BigEndian>(requires.requires_version_index)?;
+            }
+
+            write_count(bytes, attribute_module.exports.len(), "exports_count")?;
+            for exports in &attribute_module.exports {
+                bytes.write_u16::<BigEndian>(exports.exports_index)?;
+                bytes.write_u16::<BigEndian>(exports.exports_flags)?;
+                write_u16_table(bytes, &exports.exports_to_index, "exports_to_count")?;
+            }
+
+            write_count(bytes, attribute_module.opens.len(), "opens_count")?;
+            for opens in &attribute_module.opens {
+                bytes.write_u16::<BigEndian>(opens.opens_index)?;
+                bytes.write_u16::<BigEndian>(opens.opens_flags)?;
+                write_u16_table(bytes, &opens.opens_to_index, "opens_to_count")?;
+            }
+
+            write_u16_table(bytes, &attribute_module.uses_index, "uses_count")?;
+
+            write_count(bytes, attribute_module.provides.len(), "provides_count")?;
+            for provides in &attribute_module.provides {
+                bytes.write_u16::<BigEndian>(provides.provides_index)?;
+                write_u16_table(bytes, &provides.provides_with_index, "provides_with_count")?;
+            }
+        },
+        AttributeInfo::ModulePackages(attribute_module_packages) => write_u16_table(bytes, &attribute_module_packages.package_index, "package_count")?,
+        AttributeInfo::ModuleMainClass(attribute_module_main_class) => bytes.write_u16::<BigEndian>(attribute_module_main_class.main_class_index)?,
+        AttributeInfo::NestHost(attribute_nest_host) => bytes.write_u16::<BigEndian>(attribute_nest_host.host_class_index)?,
+        AttributeInfo::NestMembers(attribute_nest_members) => write_u16_table(bytes, &attribute_nest_members.classes, "number_of_classes")?,
+        AttributeInfo::Record(attribute_record) => {
+            write_count(bytes, attribute_record.components.len(), "components_count")?;
+            for component in &attribute_record.components {
+                bytes.write_u16::<BigEndian>(component.name_index)?;
+                bytes.write_u16::<BigEndian>(component.descriptor_index)?;
+                write_attributes(bytes, &component.attributes, attribute_name_indices)?;
+            }
+        },
+        AttributeInfo::PermittedSubclasses(attribute_permitted_subclasses) => write_u16_table(bytes, &attribute_permitted_subclasses.classes, "number_of_classes")?,
+    };
+
+    return Ok(());
+}
+
+fn write_stack_map_frame(bytes: &mut Vec<u8>, frame: &StackMapFrame) -> Result<(), Box<dyn error::Error>> {
+    match frame {
+        StackMapFrame::SameFrame(frame) => bytes.write_u8(frame.frame_type)?,
+        StackMapFrame::SameLocals1StackItemFrame(frame) => {
+            bytes.write_u8(frame.frame_type)?;
+            write_verification_type_info(bytes, &frame.stack_entry)?;
+        },
+        StackMapFrame::SameLocals1StackItemFrameExtended(frame) => {
+            bytes.write_u8(frame.frame_type)?;
+            bytes.write_u16::<BigEndian>(frame.offset_delta)?;
+            write_verification_type_info(bytes, &frame.stack_entry)?;
+        },
+        StackMapFrame::ChopFrame(frame) => {
+            bytes.write_u8(frame.frame_type)?;
+            bytes.write_u16::<BigEndian>(frame.offset_delta)?;
+        },
+        StackMapFrame::SameFrameExtended(frame) => {
+            bytes.write_u8(frame.frame_type)?;
+            bytes.write_u16::<BigEndian>(frame.offset_delta)?;
+        },
+        StackMapFrame::AppendFrame(frame) => {
+            // število lokalnih spremenljivk določa frame_type, zato se mora ujemati
+            if usize::from(frame.frame_type.wrapping_sub(251)) != frame.locals.len() {
+                return Err(WriteError { msg: format!("AppendFrame s frame_type {} ima {} lokalnih spremenljivk", frame.frame_type, frame.locals.len()) }.into());
+            }
+
+            bytes.write_u8(frame.frame_type)?;
+            bytes.write_u16::<BigEndian>(frame.offset_delta)?;
+            for local in &frame.locals {
+                write_verification_type_info(bytes, local)?;
+            }
+        },
+        StackMapFrame::FullFrame(frame) => {
+            bytes.write_u8(frame.frame_type)?;
+            bytes.write_u16::<BigEndian>(frame.offset_delta)?;
+            write_count(bytes, frame.locals.len(), "number_of_locals")?;
+            for local in &frame.locals {
+                write_verification_type_info(bytes, local)?;
+            }
+            write_count(bytes, frame.stack.len(), "number_of_stack_items")?;
+            for stack_item in &frame.stack {
+                write_verification_type_info(bytes, stack_item)?;
+            }
+        },
+    };
+
+    return Ok(());
+}
+
+fn write_verification_type_info(bytes: &mut Vec<u8>, verification_type_info: &VerificationTypeInfo) -> Result<(), Box<dyn error::Error>> {
+    bytes.write_u8(verification_type_info.tag())?;
+    match verification_type_info {
+        VerificationTypeInfo::ObjectVariable(info) => bytes.write_u16::<BigEndian>(info.cpool_index)?,
+        VerificationTypeInfo::UninitializedVariable(info) => bytes.write_u16::<BigEndian>(info.offset)?,
+        _ => (),
+    };
+
+    return Ok(());
+}
+
+fn write_annotations(bytes: &mut Vec<u8>, annotations: &[AttributeRuntimeAnnotationsEntry]) -> Result<(), Box<dyn error::Error>> {
+    write_count(bytes, annotations.len(), "num_annotations")?;
+    for annotation in annotations {
+        write_runtime_annotations_entry(bytes, annotation)?;
+    }
+
+    return Ok(());
+}
+
+fn write_parameter_annotations(bytes: &mut Vec<u8>, parameter_annotations: &[Vec<AttributeRuntimeAnnotationsEntry>]) -> Result<(), Box<dyn error::Error>> {
+    write_count_u8(bytes, parameter_annotations.len(), "num_parameters")?;
+    for annotations in parameter_annotations {
+        write_annotations(bytes, annotations)?;
+    }
+
+    return Ok(());
+}
+
+fn write_runtime_annotations_entry(bytes: &mut Vec<u8>, annotation: &AttributeRuntimeAnnotationsEntry) -> Result<(), Box<dyn error::Error>> {
+    bytes.write_u16::<BigEndian>(annotation.type_index)?;
+    write_count(bytes, annotation.element_value_pairs.len(), "num_element_value_pairs")?;
+    for pair in &annotation.element_value_pairs {
+        bytes.write_u16::<BigEndian>(pair.element_name_index)?;
+        write_annotations_element_value(bytes, &pair.element_value)?;
+    }
+
+    return Ok(());
+}
+
+fn write_annotations_element_value(bytes: &mut Vec<u8>, element_value: &AttributeAnnotationsElementValue) -> Result<(), Box<dyn error::Error>> {
+    bytes.write_u8(element_value.tag())?;
+    match element_value {
+        AttributeAnnotationsElementValue::Byte(value) => bytes.write_u16::<BigEndian>(value.const_value_index)?,
+        AttributeAnnotationsElementValue::Char(value) => bytes.write_u16::<BigEndian>(value.const_value_index)?,
+        AttributeAnnotationsElementValue::Double(value) => bytes.write_u16::<BigEndian>(value.const_value_index)?,
+        AttributeAnnotationsElementValue::Float(value) => bytes.write_u16::<BigEndian>(value.const_value_index)?,
+        AttributeAnnotationsElementValue::Int(value) => bytes.write_u16::<BigEndian>(value.const_value_index)?,
+        AttributeAnnotationsElementValue::Long(value) => bytes.write_u16::<BigEndian>(value.const_value_index)?,
+        AttributeAnnotationsElementValue::Short(value) => bytes.write_u16::<BigEndian>(value.const_value_index)?,
+        AttributeAnnotationsElementValue::Boolean(value) => bytes.write_u16::<BigEndian>(value.const_value_index)?,
+        AttributeAnnotationsElementValue::String(value) => bytes.write_u16::<BigEndian>(value.const_value_index)?,
+        AttributeAnnotationsElementValue::EnumClass(value) => {
+            bytes.write_u16::<BigEndian>(value.type_name_index)?;
+            bytes.write_u16::<BigEndian>(value.const_name_index)?;
+        },
+        AttributeAnnotationsElementValue::Class(value) => bytes.write_u16::<BigEndian>(value.class_info_index)?,
+        AttributeAnnotationsElementValue::AnnotationInterface(value) => write_runtime_annotations_entry(bytes, &value.annotation_value)?,
+        AttributeAnnotationsElementValue::ArrayType(value) => {
+            write_count(bytes, value.values.len(), "num_values")?;
+            for value in &value.values {
+                write_annotations_element_value(bytes, value)?;
+            }
+        },
+    };
+
+    return Ok(());
+}
+
+fn write_type_annotations(bytes: &mut Vec<u8>, annotations: &[AttributeRuntimeTypeAnnotationsEntry]) -> Result<(), Box<dyn error::Error>> {
+    write_count(bytes, annotations.len(), "num_annotations")?;
+    for annotation in annotations {
+        bytes.write_u8(annotation.target_type)?;
+        match &annotation.target_info {
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::TypeParameterTarget(target) => bytes.write_u8(target.type_parameter_index)?,
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::SuperTypeTarget(target) => bytes.write_u16::<BigEndian>(target.supertype_index)?,
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::TypeParameterBoundTarget(target) => {
+                bytes.write_u8(target.type_parameter_index)?;
+                bytes.write_u8(target.bound_index)?;
+            },
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::EmptyTarget(_) => (),
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::FormalParameterTarget(target) => bytes.write_u8(target.formal_parameter_index)?,
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::ThrowsTarget(target) => bytes.write_u16::<BigEndian>(target.throws_type_index)?,
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::LocalvarTarget(target) => {
+                write_count(bytes, target.table.len(), "table_length")?;
+                for entry in &target.table {
+                    bytes.write_u16::<BigEndian>(entry.start_pc)?;
+                    bytes.write_u16::<BigEndian>(entry.length)?;
+                    bytes.write_u16::<BigEndian>(entry.index)?;
+                }
+            },
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::CatchTarget(target) => bytes.write_u16::<BigEndian>(target.exception_table_index)?,
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::OffsetTarget(target) => bytes.write_u16::<BigEndian>(target.offset)?,
+            AttributeRuntimeTypeAnnotationsEntryTargetInfo::TypeArgumentTarget(target) => {
+                bytes.write_u16::<BigEndian>(target.offset)?;
+                bytes.write_u8(target.type_argument_index)?;
+            },
+        };
+
+        write_count_u8(bytes, annotation.target_path.len(), "path_length")?;
+        for entry in &annotation.target_path {
+            bytes.write_u8(entry.type_path_kind)?;
+            bytes.write_u8(entry.type_argument_index)?;
+        }
+
+        bytes.write_u16::<BigEndian>(annotation.type_index)?;
+        write_count(bytes, annotation.element_value_pairs.len(), "num_element_value_pairs")?;
+        for pair in &annotation.element_value_pairs {
+            bytes.write_u16::<BigEndian>(pair.element_name_index)?;
+            write_annotations_element_value(bytes, &pair.element_value)?;
+        }
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::{fixture::{u16s, ClassBuilder}, io::Cursor, parser::{parse_one, AttributeInfo, CpInfo, CpUtf8}};
+
+    /** Razredi, ki jih prevede javac 17 iz testdata/Seed.java (javac --release 17 -g Seed.java). */
+    const JAVAC_CLASSES: [&[u8]; 8] = [
+        include_bytes!("../testdata/Seed.class"),
+        include_bytes!("../testdata/Seed$Circle.class"),
+        include_bytes!("../testdata/Seed$Color.class"),
+        include_bytes!("../testdata/Seed$Inner.class"),
+        include_bytes!("../testdata/Seed$Marker.class"),
+        include_bytes!("../testdata/Seed$Point.class"),
+        include_bytes!("../testdata/Seed$Shape.class"),
+        include_bytes!("../testdata/Seed$Square.class"),
+    ];
+
+    fn sample_class() -> ClassBuilder {
+        let mut builder = ClassBuilder::new("p/Sample");
+
+        let big = builder.long(1 << 40);
+        let constant_value = builder.attribute("ConstantValue", &u16s(&[big]));
+        builder.field(0x0019, "BIG", "J", &[constant_value]);
+
+        let init = builder.method_ref("java/lang/Object", "<init>", "()V");
+        let line_number_table = builder.attribute("LineNumberTable", &u16s(&[1, 0, 3]));
+        let code = builder.code(1, 1, &[0x2a, 0xb7, (init >> 8) as u8, init as u8, 0xb1], &[], &[line_number_table]);
+        builder.method(0x0001, "<init>", "()V", &[code]);
+
+        // iload_0, ifeq 6, iconst_1, ireturn, iconst_0, ireturn
+        let exception = builder.class("java/lang/Exception");
+        let stack_map_table = builder.attribute("StackMapTable", &[&u16s(&[1])[..], &[6]].concat());
+        let code = builder.code(1, 1, &[0x1a, 0x99, 0x00, 0x05, 0x04, 0xac, 0x03, 0xac], &[[0, 4, 6, exception]], &[stack_map_table]);
+        let deprecated = builder.attribute("Deprecated", &[]);
+        builder.method(0x0009, "f", "(I)I", &[code, deprecated]);
+
+        let annotation_type = builder.utf8("Lp/Marker;");
+        let element_name = builder.utf8("value");
+        let element_value = builder.utf8("x");
+        let annotations = builder.attribute("RuntimeVisibleAnnotations", &[&u16s(&[1, annotation_type, 1, element_name])[..], b"s", &u16s(&[element_value])].concat());
+        builder.class_attribute(annotations);
+        let source_file = builder.utf8("Sample.java");
+        let source_file = builder.attribute("SourceFile", &u16s(&[source_file]));
+        builder.class_attribute(source_file);
+
+        return builder;
+    }
+
+    /** Razred z vnosi vseh vrst v constant poolu, tudi takimi, ki jih ClassBuilder ne pozna. */
+    fn constants_class() -> ClassBuilder {
+        let mut builder = ClassBuilder::new("p/Constants");
+        builder.integer(-1);
+        builder.float(1.5);
+        builder.long(i64::MIN);
+        builder.double(f64::NAN);
+        builder.string("\u{0}ž\u{FFFF}");
+        let field_ref = builder.field_ref("p/Constants", "x", "I");
+        let method_ref = builder.method_ref("p/Constants", "m", "()V");
+        let interface = builder.class("p/I");
+        let name_and_type = builder.name_and_type("n", "()V");
+        let interface_method_ref = builder.entry(11, &u16s(&[interface, name_and_type]));
+        builder.entry(15, &[&[1][..], &u16s(&[field_ref])].concat());
+        builder.entry(15, &[&[5][..], &u16s(&[method_ref])].concat());
+        builder.entry(15, &[&[9][..], &u16s(&[interface_method_ref])].concat());
+        let descriptor = builder.utf8("(I)V");
+        builder.entry(16, &u16s(&[descriptor]));
+        builder.field(0x0002, "x", "I", &[]);
+
+        return builder;
+    }
+
+    /** Prazen vmesnik brez članov in atributov. */
+    fn empty_interface() -> ClassBuilder {
+        let mut builder = ClassBuilder::new("p/Empty");
+        builder.access_flags = 0x0601;
+
+        return builder;
+    }
+
+    /** Semenski korpus za preverjanje round-tripa: razredi iz javac in ročno sestavljeni razredi. */
+    fn seed_corpus() -> Vec<Vec<u8>> {
+        let mut corpus: Vec<Vec<u8>> = JAVAC_CLASSES.iter().map(|bytes| bytes.to_vec()).collect();
+        corpus.push(sample_class().bytes());
+        corpus.push(constants_class().bytes());
+        corpus.push(empty_interface().bytes());
+
+        return corpus;
+    }
+
+    /** Če se bytes razčlenijo, morajo se zapisati in razčleniti nazaj v enak class. Vrne, ali so se razčlenili. */
+    fn check_round_trip(bytes: &[u8]) -> bool {
+        let Ok(class_file) = parse_one(&mut &bytes[..]) else {
+            return false;
+        };
+
+        let written = class_file.to_bytes().unwrap();
+        assert_eq!(parse_one(&mut &written[..]).unwrap(), class_file);
+
+        return true;
+    }
+
+    #[test]
+    fn seed_corpus_round_trips() {
+        for bytes in seed_corpus() {
+            assert!(check_round_trip(&bytes));
+        }
+    }
+
+    #[test]
+    fn mutated_seed_corpus_round_trips() {
+        // deterministični xorshift namesto naključnih mutacij, da test vedno preveri iste vhode
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = move |bound: usize| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            return (state % bound as u64) as usize;
+        };
+
+        let mut parsed = 0;
+        for seed in seed_corpus() {
+            for _ in 0..500 {
+                let mut bytes = seed.clone();
+                for _ in 0..1 + next(3) {
+                    let position = next(bytes.len());
+                    bytes[position] = next(256) as u8;
+                }
+                if next(8) == 0 {
+                    bytes.truncate(next(bytes.len()));
+                }
+
+                if check_round_trip(&bytes) {
+                    parsed += 1;
+                }
+            }
+        }
+
+        // del mutacij (npr. spremenjeni indeksi ali zastavice) mora ostati veljaven, sicer test ne preveri ničesar
+        assert!(parsed > 500, "{}", parsed);
+    }
+
+    #[test]
+    fn round_trip_is_byte_identical() {
+        let builder = sample_class();
+        let class_file = builder.parse();
+
+        let bytes = class_file.to_bytes().unwrap();
+
+        assert_eq!(bytes, builder.bytes());
+        assert_eq!(parse_one(&mut Cursor::new(bytes)).unwrap(), class_file);
+    }
+
+    #[test]
+    fn attribute_length_is_computed_from_content() {
+        let mut class_file = sample_class().parse();
+        let AttributeInfo::Code(attribute_code) = &mut class_file.methods[0].attributes[0] else {
+            panic!("pričakovan Code");
+        };
+        let attribute_length = attribute_code.attribute_length;
+        attribute_code.code.insert(0, 0x00);
+
+        let reparsed = parse_one(&mut Cursor::new(class_file.to_bytes().unwrap())).unwrap();
+
+        let AttributeInfo::Code(attribute_code) = &reparsed.methods[0].attributes[0] else {
+            panic!("pričakovan Code");
+        };
+        assert_eq!(attribute_code.attribute_length, attribute_length + 1);
+        assert_eq!(attribute_code.code[0], 0x00);
+    }
+
+    #[test]
+    fn missing_attribute_name_is_error() {
+        let mut class_file = sample_class().parse();
+        let source_file_name = class_file.constant_pool.iter_mut().find(|entry| matches!(entry, CpInfo::Utf8(cp_utf8) if cp_utf8.converted == "SourceFile")).unwrap();
+        *source_file_name = CpInfo::Utf8(CpUtf8 { bytes: b"Other".to_vec(), converted: "Other".into() });
+
+        assert!(class_file.to_bytes().is_err());
+    }
+}
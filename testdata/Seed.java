@@ -0,0 +1,77 @@
+import java.io.Serializable;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.function.Function;
+
+/** Izvorna koda razredov v semenskem korpusu (javac --release 17 -g Seed.java). */
+public class Seed<T extends Comparable<T>> implements Serializable {
+    public static final long BIG = 1L << 40;
+    public static final double RATIO = 0.5;
+    public static final String NAME = "seed";
+
+    @Retention(RetentionPolicy.RUNTIME)
+    public @interface Marker {
+        String value() default "x";
+        int[] numbers() default {1, 2};
+    }
+
+    public enum Color { RED, GREEN, BLUE }
+
+    public record Point(int x, int y) {}
+
+    public sealed interface Shape permits Circle, Square {
+        default double area() {
+            return 0;
+        }
+    }
+
+    public static final class Circle implements Shape {}
+
+    public static final class Square implements Shape {}
+
+    private final List<T> items = new ArrayList<>();
+
+    class Inner {
+        int size() {
+            return items.size();
+        }
+    }
+
+    @Marker("seed")
+    public <R> List<R> map(@Marker Function<? super T, ? extends R> function) {
+        List<R> result = new ArrayList<>();
+        items.forEach(item -> result.add(function.apply(item)));
+        return result;
+    }
+
+    static int classify(int value, Color color) throws Exception {
+        int result;
+        switch (value) {
+            case 1: result = 10; break;
+            case 2: result = 20; break;
+            case 3: result = 30; break;
+            default: result = 0;
+        }
+        switch (value) {
+            case 1: result += 1; break;
+            case 1000: result += 2; break;
+            case -50000: result += 3; break;
+        }
+        try {
+            result += color.ordinal();
+        }
+        catch (NullPointerException | IllegalStateException e) {
+            throw new Exception(e);
+        }
+        finally {
+            result++;
+        }
+        long wide = result;
+        double real = wide * RATIO;
+        synchronized (Seed.class) {
+            return (int) real + NAME.length();
+        }
+    }
+}